@@ -0,0 +1,47 @@
+use rustmate::board::Board;
+use rustmate::parser::from_path;
+use std::env;
+use std::io::{self, BufRead};
+
+#[doc = "Runs a single REPL command against the board and returns the text to print."]
+fn run_command(board: &mut Board, line: &str) -> String {
+    let words = line.split_whitespace().collect::<Vec<&str>>();
+    match words.as_slice() {
+        ["show"] => board.to_string().trim_end().to_string(),
+        ["result"] => board.finish_game().to_string(),
+        ["fen"] => board.to_fen(),
+        ["move", from, to] => match (board.parse_square(from), board.parse_square(to)) {
+            (Some(from), Some(to)) => match board.apply_move_mut(from, to) {
+                Ok(_) => "ok".to_string(),
+                Err(err) => format!("ERROR: [{}]", err),
+            },
+            _ => "ERROR: [Invalid square]".to_string(),
+        },
+        ["undo"] => match board.undo() {
+            Ok(()) => "ok".to_string(),
+            Err(err) => format!("ERROR: [{}]", err),
+        },
+        _ => format!("ERROR: [Unknown command: {}]", line.trim()),
+    }
+}
+
+fn main() {
+    let Some(filename) = env::args().nth(1) else {
+        eprintln!("ERROR: [Please provide a filename as an argument]");
+        std::process::exit(1);
+    };
+    let mut board = match from_path(&filename) {
+        Ok(board) => board,
+        Err(err) => {
+            eprintln!("ERROR: [{}]", err);
+            std::process::exit(1);
+        }
+    };
+    for line in io::stdin().lock().lines().map_while(Result::ok) {
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => break,
+            _ => println!("{}", run_command(&mut board, &line)),
+        }
+    }
+}
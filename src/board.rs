@@ -1,21 +1,101 @@
-use crate::piece::{Color, Piece};
+use crate::piece::{Color, Orientation, Piece, PieceKind};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Display;
 
+#[cfg(feature = "ansi")]
+const ANSI_RESET: &str = "\x1b[0m";
+#[cfg(feature = "ansi")]
+const ANSI_LIGHT_SQUARE: &str = "\x1b[43m";
+#[cfg(feature = "ansi")]
+const ANSI_DARK_SQUARE: &str = "\x1b[42m";
+#[cfg(feature = "ansi")]
+const ANSI_WHITE_PIECE: &str = "\x1b[1;97m";
+#[cfg(feature = "ansi")]
+const ANSI_BLACK_PIECE: &str = "\x1b[1;30m";
+
+#[doc = "A move from one position to another, as a (from, to) pair."]
+pub type Move = ((usize, usize), (usize, usize));
+
+#[doc = "The column where kings start, and from where they castle."]
+const CASTLING_KING_COLUMN: usize = 4;
+
+#[doc = "The key mixed into Board::position_key when Black is to move."]
+const BLACK_TO_MOVE_KEY: u64 = 0x6A09_E667_F3BC_C908;
+
+#[doc = "The bonus added to a piece's value by Board::weighted_balance, indexed by square of an 8x8 board.
+Central squares are worth the most. Larger and smaller boards are scaled onto this table."]
+const POSITIONAL_BONUS: [[f32; 8]; 8] = [
+    [0.00, 0.05, 0.05, 0.05, 0.05, 0.05, 0.05, 0.00],
+    [0.05, 0.10, 0.10, 0.10, 0.10, 0.10, 0.10, 0.05],
+    [0.05, 0.10, 0.20, 0.20, 0.20, 0.20, 0.10, 0.05],
+    [0.05, 0.10, 0.20, 0.30, 0.30, 0.20, 0.10, 0.05],
+    [0.05, 0.10, 0.20, 0.30, 0.30, 0.20, 0.10, 0.05],
+    [0.05, 0.10, 0.20, 0.20, 0.20, 0.20, 0.10, 0.05],
+    [0.05, 0.10, 0.10, 0.10, 0.10, 0.10, 0.10, 0.05],
+    [0.00, 0.05, 0.05, 0.05, 0.05, 0.05, 0.05, 0.00],
+];
+
+#[doc = "returns a well mixed 64 bit value for a given seed (SplitMix64)."]
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[doc = "returns the Zobrist key of a piece standing on its position."]
+fn zobrist_key(piece: &Piece) -> u64 {
+    let kind = match piece.kind {
+        PieceKind::King => 0,
+        PieceKind::Queen => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Bishop => 3,
+        PieceKind::Knight => 4,
+        PieceKind::Pawn => 5,
+    };
+    let color = if piece.is_white() { 0 } else { 1 };
+    let (x, y) = piece.get_position();
+    splitmix64((x as u64) << 32 | (y as u64) << 8 | kind << 1 | color)
+}
+
 #[doc = "An enum representing the winner of the game."]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Winner {
     White,
     Black,
     Draw,
 }
 
+impl Winner {
+    #[doc = "returns the winner on the other side, a draw staying a draw."]
+    pub fn opposite(&self) -> Winner {
+        match self {
+            Winner::White => Winner::Black,
+            Winner::Black => Winner::White,
+            Winner::Draw => Winner::Draw,
+        }
+    }
+}
+
 #[doc = "An enum representing errors that can occur while manipulating the board."]
 #[derive(Debug, PartialEq)]
 pub enum BoardError {
     InvalidPosition,
     PositionOccupied,
+    EmptySquare,
+    NotYourTurn,
+    IllegalMove,
+    KingInCheck,
+    NothingToUndo,
+    InvalidPromotion,
+    InvalidUci,
+    InvalidSize(usize),
+    UnsupportedOrientation,
+    InMove {
+        index: usize,
+        error: Box<BoardError>,
+    },
 }
 
 impl Error for BoardError {}
@@ -25,52 +105,188 @@ impl Display for BoardError {
         match self {
             BoardError::InvalidPosition => write!(f, "Invalid position"),
             BoardError::PositionOccupied => write!(f, "Position occupied"),
+            BoardError::EmptySquare => write!(f, "Empty square"),
+            BoardError::NotYourTurn => write!(f, "Not your turn"),
+            BoardError::IllegalMove => write!(f, "Illegal move"),
+            BoardError::KingInCheck => write!(f, "Move leaves the king in check"),
+            BoardError::NothingToUndo => write!(f, "Nothing to undo"),
+            BoardError::InvalidPromotion => write!(f, "Invalid promotion"),
+            BoardError::InvalidUci => write!(f, "Invalid UCI move"),
+            BoardError::InvalidSize(size) => write!(f, "Invalid board size: {}", size),
+            BoardError::UnsupportedOrientation => write!(f, "Not supported on a horizontal board"),
+            BoardError::InMove { index, error } => write!(f, "Move {}: {}", index, error),
         }
     }
 }
 
-#[doc = "A struct representing the chess board."]
-#[derive(Debug)]
+#[doc = "A struct recording a move applied to the board, with the piece it captured and the kind a pawn was promoted to, if any."]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveRecord {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+    pub captured: Option<Piece>,
+    pub promotion: Option<PieceKind>,
+    castling: CastlingRights,
+}
+
+#[doc = "A struct holding the board resulting from a move and the piece the move captured, if any."]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveResult {
+    pub board: Board,
+    pub captured: Option<Piece>,
+}
+
+#[doc = "A struct describing how a square differs between two boards."]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquareChange {
+    pub position: (usize, usize),
+    pub before: Option<Piece>,
+    pub after: Option<Piece>,
+}
+
+#[doc = "A struct bundling the metrics of a board most dashboards show, see Board::summary."]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoardSummary {
+    pub white_pieces: usize,
+    pub black_pieces: usize,
+    pub material_balance: i32,
+    pub turn: Color,
+    pub result: char,
+}
+
+#[doc = "A struct holding which castling moves each side may still make.
+A right is lost once the king or the matching rook leaves its starting square."]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CastlingRights {
+    pub white_kingside: bool,
+    pub white_queenside: bool,
+    pub black_kingside: bool,
+    pub black_queenside: bool,
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        CastlingRights {
+            white_kingside: true,
+            white_queenside: true,
+            black_kingside: true,
+            black_queenside: true,
+        }
+    }
+}
+
+impl CastlingRights {
+    #[doc = "returns whether a color may still castle on the kingside or the queenside."]
+    fn allows(&self, color: Color, kingside: bool) -> bool {
+        match (color, kingside) {
+            (Color::White, true) => self.white_kingside,
+            (Color::White, false) => self.white_queenside,
+            (Color::Black, true) => self.black_kingside,
+            (Color::Black, false) => self.black_queenside,
+        }
+    }
+    #[doc = "removes the right of a color to castle on the kingside or the queenside."]
+    fn revoke(&mut self, color: Color, kingside: bool) {
+        match (color, kingside) {
+            (Color::White, true) => self.white_kingside = false,
+            (Color::White, false) => self.white_queenside = false,
+            (Color::Black, true) => self.black_kingside = false,
+            (Color::Black, false) => self.black_queenside = false,
+        }
+    }
+}
+
+#[doc = "A struct representing the chess board.
+Cloning a board is cheap since pieces are Copy, which lets moves be tried on copies of a position."]
+#[derive(Debug, Clone)]
 pub struct Board {
     squares: HashMap<(usize, usize), Option<Piece>>,
     turn: Color,
+    history: Vec<MoveRecord>,
+    size: usize,
+    castling: CastlingRights,
+    orientation: Orientation,
+    max_range: Option<usize>,
 }
 
+#[doc = "Boards are equal when they have the same size, turn and pieces. Empty squares are equal whether
+they are stored or not, and the history is ignored."]
 impl PartialEq for Board {
     fn eq(&self, other: &Self) -> bool {
-        if self.turn != other.turn {
-            return false;
-        }
-        let mut pieces = self
-            .squares
-            .values()
-            .filter_map(|v| v.as_ref())
-            .collect::<Vec<&Piece>>();
-        let mut other_pieces = other
-            .squares
-            .values()
-            .filter_map(|v| v.as_ref())
-            .collect::<Vec<&Piece>>();
-        pieces.sort_by_key(|p| p.get_position());
-        other_pieces.sort_by_key(|p| p.get_position());
-        pieces == other_pieces
+        let size = self.size;
+        self.size == other.size
+            && self.turn == other.turn
+            && (0..size).all(|x| (0..size).all(|y| self.piece_at((x, y)) == other.piece_at((x, y))))
+    }
+}
+
+#[doc = "Displays the board the way board files are written, so that it can be parsed back."]
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render_with('_', " "))
     }
 }
 
 impl Board {
+    #[doc = "The result returned by finish_game when White wins (Blancas)."]
+    pub const RESULT_WHITE: char = 'B';
+    #[doc = "The result returned by finish_game when Black wins (Negras)."]
+    pub const RESULT_BLACK: char = 'N';
+    #[doc = "The result returned by finish_game when both sides can capture (Empate)."]
+    pub const RESULT_DRAW: char = 'E';
+    #[doc = "The result returned by finish_game when no side can capture (Pierden)."]
+    pub const RESULT_NONE: char = 'P';
+    #[doc = "The largest board size, so that every file can be named by a letter from a to z."]
+    pub const MAX_SIZE: usize = 26;
+
     #[doc = "returns a new Board instance with an empty board and the White player's turn."]
     pub fn default_board() -> Self {
+        Self::default_board_with_turn(Color::White)
+    }
+    #[doc = "returns a new Board instance with an empty board and the given player's turn."]
+    pub fn default_board_with_turn(turn: Color) -> Self {
+        let mut board = Self::empty(8);
+        board.turn = turn;
+        board
+    }
+    #[doc = "returns a new Board instance with an empty size x size board and the White player's turn.
+    A size of 0 or larger than MAX_SIZE returns an InvalidSize error."]
+    pub fn with_size(size: usize) -> Result<Self, BoardError> {
+        if size == 0 || size > Self::MAX_SIZE {
+            return Err(BoardError::InvalidSize(size));
+        }
+        Ok(Self::empty(size))
+    }
+    #[doc = "returns an empty size x size board, for sizes already known to be valid."]
+    fn empty(size: usize) -> Self {
         let mut squares = HashMap::new();
-        for x in 0..8 {
-            for y in 0..8 {
+        for x in 0..size {
+            for y in 0..size {
                 squares.insert((x, y), None);
             }
         }
         Board {
             squares,
             turn: Color::White,
+            history: Vec::new(),
+            size,
+            castling: CastlingRights::default(),
+            orientation: Orientation::default(),
+            max_range: None,
         }
     }
+    #[doc = "returns a BoardBuilder to construct a board fluently."]
+    pub fn builder() -> BoardBuilder {
+        BoardBuilder::new()
+    }
+    #[doc = "returns the number of rows and columns of the board."]
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    #[doc = "returns a boolean indicating whether a given position is inside the board."]
+    pub fn is_valid_position(&self, position: (usize, usize)) -> bool {
+        position.0 < self.size && position.1 < self.size
+    }
     #[doc = "returns a boolean indicating whether a given position on the board is empty or not."]
     fn is_position_empty(&self, position: (usize, usize)) -> bool {
         match self.squares.get(&position) {
@@ -78,11 +294,16 @@ impl Board {
             None => true,
         }
     }
+    #[doc = "returns a boolean indicating whether a given position is an empty square of the board.
+    A position off the board is not a square, so it returns false and nothing can be placed there."]
+    pub fn is_empty_at(&self, position: (usize, usize)) -> bool {
+        self.is_valid_position(position) && self.is_position_empty(position)
+    }
     #[doc = "places a Piece on the board at a given position. If the position is invalid or occupied, it returns an error."]
     pub fn place_piece(&mut self, piece: Piece) -> Result<(), BoardError> {
         let position = piece.get_position();
         match position {
-            (x, y) if x > 7 || y > 7 => Err(BoardError::InvalidPosition),
+            pos if !self.is_valid_position(pos) => Err(BoardError::InvalidPosition),
             pos if !self.is_position_empty(pos) => Err(BoardError::PositionOccupied),
             pos => {
                 self.squares.insert(pos, Some(piece));
@@ -90,153 +311,1343 @@ impl Board {
             }
         }
     }
+    #[doc = "places a Piece on the board at a given position, replacing whatever piece stands there.
+    If the position is invalid, it returns an error. Use remove_piece first to get hold of the replaced piece."]
+    pub fn set_piece(&mut self, piece: Piece) -> Result<(), BoardError> {
+        let position = piece.get_position();
+        if !self.is_valid_position(position) {
+            return Err(BoardError::InvalidPosition);
+        }
+        self.squares.insert(position, Some(piece));
+        Ok(())
+    }
+    #[doc = "removes and returns the Piece at a given position. If the position is invalid or empty, it returns an error."]
+    pub fn remove_piece(&mut self, position: (usize, usize)) -> Result<Piece, BoardError> {
+        if !self.is_valid_position(position) {
+            return Err(BoardError::InvalidPosition);
+        }
+        self.squares
+            .get_mut(&position)
+            .and_then(Option::take)
+            .ok_or(BoardError::EmptySquare)
+    }
+    #[doc = "swaps the contents of two positions, whether they hold a piece or not, updating the position of the moved pieces.
+    If either position is invalid, it returns an error and the board is left unchanged."]
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) -> Result<(), BoardError> {
+        if !self.is_valid_position(a) || !self.is_valid_position(b) {
+            return Err(BoardError::InvalidPosition);
+        }
+        let first = self.squares.insert(a, None).flatten();
+        let second = self.squares.insert(b, None).flatten();
+        for (position, piece) in [(a, second), (b, first)] {
+            if let Some(piece) = piece {
+                self.squares
+                    .insert(position, Some(Piece { position, ..piece }));
+            }
+        }
+        Ok(())
+    }
     #[doc = "returns the Piece at a given position, if any."]
     pub fn piece_at(&self, position: (usize, usize)) -> Option<Piece> {
+        if !self.is_valid_position(position) {
+            return None;
+        }
         *self.squares.get(&position).unwrap_or(&None)
     }
+    #[doc = "returns a reference to the Piece at a given position, if any, without copying it."]
+    pub fn piece_ref(&self, position: (usize, usize)) -> Option<&Piece> {
+        self.squares
+            .get(&position)
+            .and_then(|square| square.as_ref())
+    }
+    #[doc = "returns a view of the board as its rows in row-major order, borrowing the pieces instead of copying them."]
+    pub fn as_grid(&self) -> Vec<Vec<Option<&Piece>>> {
+        (0..self.size)
+            .map(|x| (0..self.size).map(|y| self.piece_ref((x, y))).collect())
+            .collect()
+    }
+    #[doc = "returns the squares of a row of the board from the first column to the last one.
+    Row 0 is the first row of the board file, the highest rank. An index off the board returns no squares."]
+    pub fn rank(&self, r: usize) -> Vec<Option<Piece>> {
+        if r >= self.size {
+            return Vec::new();
+        }
+        (0..self.size).map(|y| self.piece_at((r, y))).collect()
+    }
+    #[doc = "returns the squares of a column of the board from the first row to the last one.
+    Column 0 is file a. An index off the board returns no squares."]
+    pub fn file(&self, f: usize) -> Vec<Option<Piece>> {
+        if f >= self.size {
+            return Vec::new();
+        }
+        (0..self.size).map(|x| self.piece_at((x, f))).collect()
+    }
+    #[doc = "returns an iterator over the pieces on the board, in no particular order."]
+    pub fn pieces(&self) -> impl Iterator<Item = &Piece> {
+        self.squares.values().filter_map(|square| square.as_ref())
+    }
+    #[doc = "returns the pieces on the board sorted by position in row-major order."]
+    pub fn sorted_pieces(&self) -> Vec<Piece> {
+        let mut pieces = self.pieces().copied().collect::<Vec<Piece>>();
+        pieces.sort();
+        pieces
+    }
+    #[doc = "returns the pieces of a given kind of both colors, in row-major order."]
+    pub fn pieces_of_kind(&self, kind: PieceKind) -> Vec<Piece> {
+        let mut pieces = self.sorted_pieces();
+        pieces.retain(|piece| piece.kind == kind);
+        pieces
+    }
+    #[doc = "returns the pieces of a given color, in row-major order."]
+    pub fn pieces_of_color(&self, color: Color) -> Vec<Piece> {
+        let mut pieces = self.sorted_pieces();
+        pieces.retain(|piece| piece.color() == color);
+        pieces
+    }
+    #[doc = "returns a boolean indicating whether there are no pieces on the board."]
+    pub fn is_empty(&self) -> bool {
+        self.pieces().next().is_none()
+    }
+    #[doc = "returns a boolean indicating whether every square of the board holds a piece."]
+    pub fn is_full(&self) -> bool {
+        (0..self.size).all(|x| (0..self.size).all(|y| !self.is_position_empty((x, y))))
+    }
+    #[doc = "returns the positions holding a piece, in row-major order."]
+    pub fn occupied_squares(&self) -> Vec<(usize, usize)> {
+        self.positions()
+            .filter(|&position| !self.is_position_empty(position))
+            .collect()
+    }
+    #[doc = "returns the positions holding no piece, in row-major order."]
+    pub fn empty_squares(&self) -> Vec<(usize, usize)> {
+        self.positions()
+            .filter(|&position| self.is_position_empty(position))
+            .collect()
+    }
     #[doc = "returns the position of the Piece of a given color, if any."]
     fn get_piece_position_based_on_turn(&self, color: Color) -> Option<(usize, usize)> {
-        self.squares.iter().find_map(|(&(x, y), square)| {
-            square.and_then(|piece| {
-                if piece.color() == color {
-                    Some((x, y))
-                } else {
-                    None
-                }
-            })
+        self.squares.iter().find_map(|(&position, square)| {
+            square
+                .filter(|piece| match color {
+                    Color::White => piece.is_white(),
+                    Color::Black => piece.is_black(),
+                })
+                .map(|_| position)
         })
     }
+    #[doc = "returns the direction pawns advance in on this board."]
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+    #[doc = "sets the direction pawns advance in on this board."]
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+    #[doc = "returns the farthest number of squares rooks, bishops and queens can reach on this board, None if unlimited."]
+    pub fn max_range(&self) -> Option<usize> {
+        self.max_range
+    }
+    #[doc = "sets the farthest number of squares rooks, bishops and queens can reach on this board, None for unlimited."]
+    pub fn set_max_range(&mut self, max_range: Option<usize>) {
+        self.max_range = max_range;
+    }
     #[doc = "returns the current turn."]
     pub fn curr_turn(&self) -> Color {
         self.turn
     }
     #[doc = "returns the next turn."]
     fn get_next_turn(&self) -> Color {
-        if self.turn == Color::White {
-            Color::Black
-        } else {
-            Color::White
-        }
+        self.turn.opposite()
     }
     #[doc = "changes the turn to the next one."]
     pub fn next_turn(&mut self) {
         self.turn = self.get_next_turn();
     }
-
-    #[doc = "returns the winner of the game, if there is one."]
-    pub fn side_to_move(&self) -> Option<Winner> {
-        let curr_piece_position = self
-            .get_piece_position_based_on_turn(self.curr_turn())
-            .unwrap();
-        let next_piece_position = self
-            .get_piece_position_based_on_turn(self.get_next_turn())
-            .unwrap();
-        let curr_piece = self.piece_at(curr_piece_position).unwrap();
-        let next_piece = self.piece_at(next_piece_position).unwrap();
-        let current_turn_has_capture = curr_piece.can_capture(next_piece_position);
-        let next_turn_has_capture = next_piece.can_capture(curr_piece_position);
-        match (current_turn_has_capture, next_turn_has_capture, self.turn) {
-            (true, false, Color::White) => Some(Winner::White),
-            (false, true, Color::White) => Some(Winner::Black),
-            (true, false, Color::Black) => Some(Winner::Black),
-            (false, true, Color::Black) => Some(Winner::White),
-            (true, true, _) => Some(Winner::Draw),
-            _ => None,
+    #[doc = "changes the turn to the next one only when the condition holds."]
+    pub fn advance_turn_if(&mut self, cond: bool) {
+        if cond {
+            self.next_turn();
         }
     }
-    #[doc = "returns the character representation of the winner of the game."]
-    pub fn finish_game(&self) -> char {
-        self.side_to_move()
-            .map(|color| match color {
-                Winner::White => 'B',
-                Winner::Black => 'N',
-                Winner::Draw => 'E',
-            })
-            .unwrap_or('P')
+    #[doc = "sets the turn to the given color."]
+    pub fn set_turn(&mut self, color: Color) {
+        self.turn = color;
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::piece::{Color, PieceKind};
 
-    #[test]
-    fn test_place_piece() -> Result<(), BoardError> {
-        let mut board = Board::default_board();
-        let position = (0, 0);
-        board.place_piece(Piece {
-            color: Color::White,
-            kind: PieceKind::King,
-            position,
-        })?;
-        let piece = board.piece_at(position);
-        assert_eq!(
-            piece,
-            Some(Piece {
-                color: Color::White,
-                kind: PieceKind::King,
-                position,
+    #[doc = "returns the squares strictly between two positions on the same row, column or diagonal, in order
+    from the first position towards the second one. Positions that are not aligned, such as a knight's jump,
+    or that are adjacent have no squares in between."]
+    pub fn squares_between(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+        let dx = to.0 as i32 - from.0 as i32;
+        let dy = to.1 as i32 - from.1 as i32;
+        if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+            return Vec::new();
+        }
+        let steps = dx.abs().max(dy.abs());
+        (1..steps)
+            .map(|step| {
+                let x = from.0 as i32 + dx.signum() * step;
+                let y = from.1 as i32 + dy.signum() * step;
+                (x as usize, y as usize)
             })
+            .collect()
+    }
+    #[doc = "returns a boolean indicating whether every square strictly between two aligned positions is empty.
+    Positions that are not on the same row, column or diagonal have nothing in between."]
+    fn path_is_clear(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        Self::squares_between(from, to)
+            .into_iter()
+            .all(|position| self.is_position_empty(position))
+    }
+    #[doc = "returns a boolean indicating whether a piece can move to a given position, capturing an enemy piece if there is one."]
+    fn can_move(&self, piece: &Piece, to: (usize, usize)) -> bool {
+        let from = piece.get_position();
+        match self.piece_at(to) {
+            Some(target) if target.color() == piece.color() => false,
+            Some(_) => self.attacks(piece, to),
+            None => {
+                from != to
+                    && piece.can_move_to_oriented(to, self.orientation)
+                    && piece.is_within_range(to, self.max_range)
+                    && self.path_is_clear(from, to)
+            }
+        }
+    }
+    #[doc = "returns a boolean indicating whether a piece attacks a given position, taking blockers into account."]
+    fn attacks(&self, piece: &Piece, target: (usize, usize)) -> bool {
+        let from = piece.get_position();
+        from != target
+            && piece.can_capture_oriented(target, self.orientation)
+            && piece.is_within_range(target, self.max_range)
+            && self.path_is_clear(from, target)
+    }
+    #[doc = "returns a boolean indicating whether the piece at one position can capture the piece at another one.
+    Unlike Piece::can_capture, the target must hold an enemy piece and the path to it must be clear."]
+    pub fn can_capture(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        match (self.piece_ref(from), self.piece_ref(to)) {
+            (Some(piece), Some(target)) => {
+                piece.color() != target.color() && self.attacks(piece, to)
+            }
+            _ => false,
+        }
+    }
+    #[doc = "returns a boolean indicating whether the piece at one position can capture the piece at another one
+    in a single legal move, whatever the turn. On top of Board::can_capture, the capture must not leave
+    the attacker's own king in check."]
+    pub fn capture_in_one(&self, attacker: (usize, usize), target: (usize, usize)) -> bool {
+        let Some(piece) = self.piece_ref(attacker) else {
+            return false;
+        };
+        if !self.can_capture(attacker, target) {
+            return false;
+        }
+        let mut board = self.clone();
+        board.set_turn(piece.color());
+        board.apply_move_mut(attacker, target).is_ok()
+    }
+    #[doc = "returns every piece of a given color attacking a square, in row-major order, taking blockers into account.
+    Pawns only attack the squares they capture on, never the square they advance to."]
+    pub fn attackers_of(&self, square: (usize, usize), by: Color) -> Vec<Piece> {
+        self.sorted_pieces()
+            .into_iter()
+            .filter(|piece| piece.color() == by && self.attacks(piece, square))
+            .collect()
+    }
+    #[doc = "returns a boolean indicating whether a given square is attacked by a piece of a given color,
+    taking blockers into account. It stops at the first attacker found."]
+    pub fn is_square_attacked(&self, square: (usize, usize), by: Color) -> bool {
+        self.pieces()
+            .filter(|piece| piece.color() == by)
+            .any(|piece| self.attacks(piece, square))
+    }
+    #[doc = "returns, for every square of the board, how many pieces of a given color attack it,
+    taking blockers into account. The grid is indexed by row and then by column."]
+    pub fn heatmap(&self, color: Color) -> Vec<Vec<u8>> {
+        let mut heatmap = vec![vec![0; self.size]; self.size];
+        for piece in self.pieces().filter(|piece| piece.color() == color) {
+            for (x, y) in self.positions() {
+                if self.attacks(piece, (x, y)) {
+                    heatmap[x][y] += 1;
+                }
+            }
+        }
+        heatmap
+    }
+    #[doc = "returns how many central squares are attacked by a given color.
+    On an 8x8 board these are d4, e4, d5 and e5."]
+    pub fn center_control(&self, color: Color) -> usize {
+        let mid = self.size / 2;
+        let center = if self.size.is_multiple_of(2) {
+            mid.saturating_sub(1)..=mid
+        } else {
+            mid..=mid
+        };
+        center
+            .clone()
+            .flat_map(|x| center.clone().map(move |y| (x, y)))
+            .filter(|&square| self.is_square_attacked(square, color))
+            .count()
+    }
+    #[doc = "returns the position of the king of a given color, if any.
+    When there are several kings the first one in row-major order is returned."]
+    pub fn king_position(&self, color: Color) -> Option<(usize, usize)> {
+        self.positions().find(|&position| {
+            self.piece_ref(position)
+                .is_some_and(|piece| piece.kind == PieceKind::King && piece.color() == color)
+        })
+    }
+    #[doc = "returns every enemy piece attacking the king of a given color, in row-major order.
+    Two checkers make a double check, and there are none when the color has no king."]
+    pub fn checkers(&self, color: Color) -> Vec<Piece> {
+        match self.king_position(color) {
+            Some(king) => self.attackers_of(king, color.opposite()),
+            None => Vec::new(),
+        }
+    }
+    #[doc = "returns a boolean indicating whether the king of a given color is attacked by an enemy piece."]
+    pub fn is_in_check(&self, color: Color) -> bool {
+        let enemy = color.opposite();
+        self.pieces()
+            .filter(|piece| piece.kind == PieceKind::King && piece.color() == color)
+            .any(|king| self.is_square_attacked(king.get_position(), enemy))
+    }
+    #[doc = "moves the piece of the side to move from one position to another, capturing whatever enemy piece stands there.
+    A pawn reaching its last rank is promoted to a queen.
+    The move is recorded in the history and the turn passes to the other side. The captured piece, if any, is returned."]
+    pub fn apply_move_mut(
+        &mut self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<Option<Piece>, BoardError> {
+        self.apply_move_mut_with_promotion(from, to, PieceKind::Queen)
+    }
+    #[doc = "same as apply_move_mut, but a pawn reaching its last rank is promoted to the given kind."]
+    pub fn apply_move_mut_with_promotion(
+        &mut self,
+        from: (usize, usize),
+        to: (usize, usize),
+        promotion: PieceKind,
+    ) -> Result<Option<Piece>, BoardError> {
+        let (piece, castling_rook) = self.validate_move(from, to)?;
+        let captured = self.piece_at(to);
+        let mut moved = Piece {
+            position: to,
+            ..piece
+        };
+        let promotion = if moved.is_on_promotion_square_oriented(self.size, self.orientation) {
+            moved.promote(promotion)?;
+            Some(promotion)
+        } else {
+            None
+        };
+        self.history.push(MoveRecord {
+            from,
+            to,
+            captured,
+            promotion,
+            castling: self.castling,
+        });
+        self.squares.insert(from, None);
+        self.squares.insert(to, Some(moved));
+        if let Some((rook_from, rook_to)) = castling_rook {
+            self.move_piece(rook_from, rook_to);
+        }
+        self.revoke_castling(from);
+        self.revoke_castling(to);
+        self.next_turn();
+        if self.is_in_check(piece.color()) {
+            self.undo()?;
+            return Err(BoardError::KingInCheck);
+        }
+        Ok(captured)
+    }
+    #[doc = "checks that the side to move may move the piece on one position to another, leaving king safety aside.
+    It returns the moving piece, along with the rook's (from, to) positions when the move castles."]
+    fn validate_move(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<(Piece, Option<Move>), BoardError> {
+        if !self.is_valid_position(from) || !self.is_valid_position(to) {
+            return Err(BoardError::InvalidPosition);
+        }
+        let piece = self.piece_at(from).ok_or(BoardError::EmptySquare)?;
+        if piece.color() != self.turn {
+            return Err(BoardError::NotYourTurn);
+        }
+        let castling_rook = self.castling_rook(&piece, to);
+        if castling_rook.is_none() && !self.can_move(&piece, to) {
+            return Err(BoardError::IllegalMove);
+        }
+        Ok((piece, castling_rook))
+    }
+    #[doc = "returns a boolean indicating whether the side to move can legally move the piece on one position
+    to another: the piece must be theirs, move the way its kind does with nothing in the way, not land on
+    a friendly piece and not leave its own king in check. Unlike apply_move, no move is recorded."]
+    pub fn is_legal_move(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        let Ok((piece, castling_rook)) = self.validate_move(from, to) else {
+            return false;
+        };
+        let mut board = self.clone();
+        board.move_piece(from, to);
+        if let Some((rook_from, rook_to)) = castling_rook {
+            board.move_piece(rook_from, rook_to);
+        }
+        !board.is_in_check(piece.color())
+    }
+    #[doc = "moves whatever stands on a position to another one, updating the piece's position."]
+    fn move_piece(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let piece = self.squares.insert(from, None).flatten();
+        self.squares.insert(
+            to,
+            piece.map(|piece| Piece {
+                position: to,
+                ..piece
+            }),
         );
-        Ok(())
     }
-
-    #[test]
-    fn test_board_are_eq() {
-        let mut board1 = Board {
-            squares: HashMap::new(),
-            turn: Color::White,
+    #[doc = "returns the row where the king and rooks of a given color start."]
+    fn home_row(&self, color: Color) -> usize {
+        match color {
+            Color::White => self.size - 1,
+            Color::Black => 0,
+        }
+    }
+    #[doc = "removes the castling rights tied to a king or rook starting position, once something moves from or to it."]
+    fn revoke_castling(&mut self, position: (usize, usize)) {
+        for color in [Color::White, Color::Black] {
+            let row = self.home_row(color);
+            if position == (row, CASTLING_KING_COLUMN) {
+                self.castling.revoke(color, true);
+                self.castling.revoke(color, false);
+            } else if position == (row, self.size - 1) {
+                self.castling.revoke(color, true);
+            } else if position == (row, 0) {
+                self.castling.revoke(color, false);
+            }
+        }
+    }
+    #[doc = "returns the castling rights of both sides."]
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castling
+    }
+    #[doc = "returns the rook's (from, to) positions if moving a piece to a given position is a legal castling move.
+    The king must move two squares towards a rook of its color on its starting square, with every square
+    between them empty, without being in check nor crossing or landing on an attacked square.
+    Home rows are ranks, so only Vertical boards can castle."]
+    fn castling_rook(
+        &self,
+        king: &Piece,
+        to: (usize, usize),
+    ) -> Option<((usize, usize), (usize, usize))> {
+        if self.orientation != Orientation::Vertical {
+            return None;
+        }
+        let row = self.home_row(king.color());
+        let from = king.get_position();
+        if king.kind != PieceKind::King || from != (row, CASTLING_KING_COLUMN) || to.0 != row {
+            return None;
+        }
+        let kingside = to.1 == CASTLING_KING_COLUMN + 2;
+        if !kingside && to.1 + 2 != CASTLING_KING_COLUMN {
+            return None;
+        }
+        if !self.castling.allows(king.color(), kingside) {
+            return None;
+        }
+        let rook_from = (row, if kingside { self.size - 1 } else { 0 });
+        let rook_to = (row, (from.1 + to.1) / 2);
+        match self.piece_at(rook_from) {
+            Some(rook) if rook.kind == PieceKind::Rook && rook.color() == king.color() => {}
+            _ => return None,
+        }
+        let enemy = king.color().opposite();
+        let safe = [from, rook_to, to]
+            .iter()
+            .all(|&square| !self.is_square_attacked(square, enemy));
+        if safe && self.path_is_clear(from, rook_from) {
+            Some((rook_from, rook_to))
+        } else {
+            None
+        }
+    }
+    #[doc = "returns a new board with a move applied along with the piece it captured, leaving this one untouched.
+    See apply_move_mut."]
+    pub fn apply_move(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Result<MoveResult, BoardError> {
+        self.apply_move_with_promotion(from, to, PieceKind::Queen)
+    }
+    #[doc = "returns a new board with a move applied, promoting a pawn that reaches its last rank to the given kind."]
+    pub fn apply_move_with_promotion(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        promotion: PieceKind,
+    ) -> Result<MoveResult, BoardError> {
+        let mut board = self.clone();
+        let captured = board.apply_move_mut_with_promotion(from, to, promotion)?;
+        Ok(MoveResult { board, captured })
+    }
+    #[doc = "returns a new board with a sequence of moves applied in order. The first move that fails
+    stops the replay, and its error is returned along with the index of the move."]
+    pub fn replay(&self, moves: &[Move]) -> Result<Board, BoardError> {
+        let mut board = self.clone();
+        for (index, &(from, to)) in moves.iter().enumerate() {
+            board
+                .apply_move_mut(from, to)
+                .map_err(|err| BoardError::InMove {
+                    index,
+                    error: Box::new(err),
+                })?;
+        }
+        Ok(board)
+    }
+    #[doc = "returns a new board with a move in UCI form applied, such as e2e4 or e7e8q for a promotion.
+    The promotion letter is one of q, r, b or n, and a pawn promotes to a queen when it is left out.
+    A malformed move or a square off the board returns an InvalidUci error. UCI ranks are rows, so a
+    Horizontal board returns an UnsupportedOrientation error."]
+    pub fn apply_move_uci(&self, uci: &str) -> Result<Board, BoardError> {
+        if self.orientation != Orientation::Vertical {
+            return Err(BoardError::UnsupportedOrientation);
+        }
+        let second_square = uci
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| c.is_ascii_lowercase())
+            .map(|(i, _)| i)
+            .ok_or(BoardError::InvalidUci)?;
+        let (from, rest) = uci.split_at(second_square);
+        let to_end = rest
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| !c.is_ascii_digit())
+            .map_or(rest.len(), |(i, _)| i);
+        let (to, promotion) = rest.split_at(to_end);
+        let promotion = match promotion {
+            "" | "q" => PieceKind::Queen,
+            "r" => PieceKind::Rook,
+            "b" => PieceKind::Bishop,
+            "n" => PieceKind::Knight,
+            _ => return Err(BoardError::InvalidUci),
         };
-        let mut board2 = Board {
-            squares: HashMap::new(),
-            turn: Color::White,
+        let from = self.parse_square(from).ok_or(BoardError::InvalidUci)?;
+        let to = self.parse_square(to).ok_or(BoardError::InvalidUci)?;
+        self.apply_move_with_promotion(from, to, promotion)
+            .map(|result| result.board)
+    }
+    #[doc = "returns every position the piece at a given position can move to, in row-major order,
+    whatever the turn. Sliding pieces stop at the first blocker, which is included when it is an enemy piece.
+    Whether the move leaves the king in check is not considered, and castling is left out."]
+    pub fn pseudo_legal_moves(&self, from: (usize, usize)) -> Vec<(usize, usize)> {
+        match self.piece_at(from) {
+            Some(piece) => self
+                .positions()
+                .filter(|&to| self.can_move(&piece, to))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+    #[doc = "returns every legal move of the side to move as (from, to) pairs, in row-major order.
+    Moves that would leave the mover's own king in check are left out."]
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        for from in self.positions() {
+            let piece = match self.piece_at(from) {
+                Some(piece) if piece.color() == self.turn => piece,
+                _ => continue,
+            };
+            for to in self.positions() {
+                let candidate =
+                    self.can_move(&piece, to) || self.castling_rook(&piece, to).is_some();
+                if candidate && self.apply_move(from, to).is_ok() {
+                    moves.push((from, to));
+                }
+            }
+        }
+        moves
+    }
+    #[doc = "returns the legal moves of the side to move that land on a square held by an enemy piece,
+    in the same order as legal_moves."]
+    pub fn legal_captures(&self) -> Vec<Move> {
+        self.legal_moves()
+            .into_iter()
+            .filter(|&(from, to)| self.is_capture(from, to))
+            .collect()
+    }
+    #[doc = "returns a boolean indicating whether moving the piece on one position to another would capture,
+    that is whether the destination holds a piece of the other color. Whether the move is legal isn't checked."]
+    pub fn is_capture(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        match (self.piece_ref(from), self.piece_ref(to)) {
+            (Some(piece), Some(target)) => piece.color() != target.color(),
+            _ => false,
+        }
+    }
+    #[doc = "returns the number of move sequences of a given depth that can be played from this position,
+    counting the leaves reached by recursively applying every legal move. Depth 0 counts the position itself."]
+    pub fn perft(&self, depth: usize) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        moves
+            .into_iter()
+            .filter_map(|(from, to)| self.apply_move(from, to).ok())
+            .map(|result| result.board.perft(depth - 1))
+            .sum()
+    }
+    #[doc = "returns an iterator over every position of the board in row-major order."]
+    fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let size = self.size;
+        (0..size).flat_map(move |x| (0..size).map(move |y| (x, y)))
+    }
+    #[doc = "returns the moves applied to the board, oldest first."]
+    pub fn history(&self) -> &[MoveRecord] {
+        &self.history
+    }
+    #[doc = "reverts the last applied move, restoring the turn, any captured piece and any promoted pawn."]
+    pub fn undo(&mut self) -> Result<(), BoardError> {
+        let record = self.history.pop().ok_or(BoardError::NothingToUndo)?;
+        let piece = self.piece_at(record.to).ok_or(BoardError::EmptySquare)?;
+        let kind = match record.promotion {
+            Some(_) => PieceKind::Pawn,
+            None => piece.kind,
         };
-        board1
-            .place_piece(Piece {
-                color: Color::White,
-                kind: PieceKind::King,
-                position: (0, 0),
-            })
-            .unwrap();
-        board1
-            .place_piece(Piece {
-                color: Color::Black,
-                kind: PieceKind::King,
-                position: (1, 1),
-            })
-            .unwrap();
-        board2
-            .place_piece(Piece {
-                color: Color::White,
-                kind: PieceKind::King,
-                position: (0, 0),
-            })
-            .unwrap();
-        board2
-            .place_piece(Piece {
-                color: Color::Black,
-                kind: PieceKind::King,
-                position: (1, 1),
-            })
-            .unwrap();
-        assert_eq!(board1, board2);
+        if piece.kind == PieceKind::King && record.from.1.abs_diff(record.to.1) == 2 {
+            let row = record.from.0;
+            let kingside = record.to.1 > record.from.1;
+            let rook_from = (row, if kingside { self.size - 1 } else { 0 });
+            let rook_to = (row, (record.from.1 + record.to.1) / 2);
+            self.move_piece(rook_to, rook_from);
+        }
+        self.castling = record.castling;
+        self.squares.insert(
+            record.from,
+            Some(Piece {
+                position: record.from,
+                kind,
+                ..piece
+            }),
+        );
+        self.squares.insert(record.to, record.captured);
+        self.turn = piece.color();
+        Ok(())
     }
 
-    #[test]
-    fn test_place_piece_invalid_position() {
-        let mut board = Board::default_board();
-        let position = (9, 9);
-        let result = board.place_piece(Piece {
-            color: Color::White,
-            kind: PieceKind::King,
-            position,
-        });
-        assert_eq!(result, Err(BoardError::InvalidPosition));
-        assert_eq!(result.unwrap_err().to_string(), "Invalid position");
+    #[doc = "returns a printable board with rank numbers down the left side and file letters along the bottom.
+    The first row of the board is the highest rank and the first column is file a."]
+    pub fn render_pretty(&self) -> String {
+        let width = self.size.to_string().len();
+        let mut out = String::new();
+        for x in 0..self.size {
+            out.push_str(&format!("{:>width$} |", self.size - x));
+            for y in 0..self.size {
+                let c = self.piece_at((x, y)).map(|p| p.to_char()).unwrap_or('_');
+                out.push(' ');
+                out.push(c);
+            }
+            out.push('\n');
+        }
+        out.push_str(&" ".repeat(width + 1));
+        out.push('+');
+        out.push_str(&"--".repeat(self.size));
+        out.push('\n');
+        out.push_str(&" ".repeat(width + 2));
+        for y in 0..self.size {
+            out.push(' ');
+            out.push((b'a' + y as u8) as char);
+        }
+        out.push('\n');
+        out
     }
 
-    #[test]
+    #[doc = "returns the board one line per row, with the given character for empty squares and
+    the given separator between squares. Display uses '_' and a single space."]
+    pub fn render_with(&self, empty: char, sep: &str) -> String {
+        let mut out = String::new();
+        for x in 0..self.size {
+            let row = (0..self.size)
+                .map(|y| self.piece_at((x, y)).map(|p| p.to_char()).unwrap_or(empty))
+                .map(String::from)
+                .collect::<Vec<String>>();
+            out.push_str(&row.join(sep));
+            out.push('\n');
+        }
+        out
+    }
+
+    #[doc = "returns the board drawn with Unicode chess symbols, one line per row, with empty squares
+    shown as middle dots."]
+    pub fn render_unicode(&self) -> String {
+        let mut out = String::new();
+        for x in 0..self.size {
+            let row = (0..self.size)
+                .map(|y| self.piece_at((x, y)).map(|p| p.glyph()).unwrap_or('·'))
+                .map(String::from)
+                .collect::<Vec<String>>();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+
+    #[doc = "returns a board colored with ANSI escape codes, alternating light and dark squares.
+    Every square is three columns wide and ends with a reset code."]
+    #[cfg(feature = "ansi")]
+    pub fn render_ansi(&self) -> String {
+        let mut out = String::new();
+        for x in 0..self.size {
+            for y in 0..self.size {
+                let background = if (x + y) % 2 == 0 {
+                    ANSI_LIGHT_SQUARE
+                } else {
+                    ANSI_DARK_SQUARE
+                };
+                out.push_str(background);
+                match self.piece_at((x, y)) {
+                    Some(piece) => {
+                        let foreground = if piece.is_white() {
+                            ANSI_WHITE_PIECE
+                        } else {
+                            ANSI_BLACK_PIECE
+                        };
+                        out.push_str(&format!("{} {} ", foreground, piece.to_char()));
+                    }
+                    None => out.push_str("   "),
+                }
+                out.push_str(ANSI_RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[doc = "returns a new board with every piece moved by a given mapping of positions, optionally swapping colors and turn."]
+    fn transformed<F>(&self, map: F, swap_colors: bool) -> Board
+    where
+        F: Fn((usize, usize)) -> (usize, usize),
+    {
+        let mut board = Board::empty(self.size);
+        board.turn = self.turn;
+        board.orientation = self.orientation;
+        board.max_range = self.max_range;
+        for piece in self.pieces() {
+            let mut piece = Piece {
+                position: map(piece.get_position()),
+                ..*piece
+            };
+            if swap_colors {
+                piece.color = piece.color().opposite();
+            }
+            board.squares.insert(piece.get_position(), Some(piece));
+        }
+        if swap_colors {
+            board.next_turn();
+        }
+        board
+    }
+    #[doc = "returns a new board mirrored top to bottom. When swap_colors is true the colors of the pieces
+    and the turn are swapped as well, showing the position from the other side's perspective."]
+    pub fn flip_vertical(&self, swap_colors: bool) -> Board {
+        let last = self.size - 1;
+        self.transformed(|(x, y)| (last - x, y), swap_colors)
+    }
+    #[doc = "returns a new board mirrored left to right."]
+    pub fn flip_horizontal(&self) -> Board {
+        let last = self.size - 1;
+        self.transformed(|(x, y)| (x, last - y), false)
+    }
+    #[doc = "returns a new board rotated by 180 degrees."]
+    pub fn rotate_180(&self) -> Board {
+        let last = self.size - 1;
+        self.transformed(|(x, y)| (last - x, last - y), false)
+    }
+    #[doc = "returns a new board rotated a quarter turn clockwise, moving the piece at (r, c) to (c, n - 1 - r).
+    Boards are always square, so the rotated board keeps the same size."]
+    pub fn rotate_cw(&self) -> Board {
+        let last = self.size - 1;
+        self.transformed(|(x, y)| (y, last - x), false)
+    }
+    #[doc = "returns a new board rotated a quarter turn counterclockwise, the inverse of rotate_cw."]
+    pub fn rotate_ccw(&self) -> Board {
+        let last = self.size - 1;
+        self.transformed(|(x, y)| (last - y, x), false)
+    }
+    #[doc = "returns a new board where every piece changes color and the turn is swapped, leaving
+    positions unchanged. Combined with flip_vertical it swaps the perspective of the players."]
+    pub fn mirror_colors(&self) -> Board {
+        self.transformed(|position| position, true)
+    }
+
+    #[doc = "returns every (attacker, victim) pair of opposite colored pieces where the attacker can capture the victim,
+    taking blockers into account. Pairs are ordered by the attacker's and then the victim's position in row-major order."]
+    pub fn capture_graph(&self) -> Vec<(Piece, Piece)> {
+        let pieces = self.sorted_pieces();
+        let mut edges = Vec::new();
+        for attacker in &pieces {
+            for victim in &pieces {
+                if attacker.color() != victim.color()
+                    && self.attacks(attacker, victim.get_position())
+                {
+                    edges.push((*attacker, *victim));
+                }
+            }
+        }
+        edges
+    }
+
+    #[doc = "returns how many pieces of a given color are on the board."]
+    pub fn count_by_color(&self, color: Color) -> usize {
+        self.pieces().filter(|piece| piece.color() == color).count()
+    }
+    #[doc = "returns a boolean indicating whether neither side has enough material left to checkmate,
+    that is a lone king against a king with at most one knight or bishop."]
+    pub fn insufficient_material(&self) -> bool {
+        let only_minor_pieces = self.pieces().all(|piece| {
+            matches!(
+                piece.kind,
+                PieceKind::King | PieceKind::Knight | PieceKind::Bishop
+            )
+        });
+        let kings = self
+            .pieces()
+            .filter(|piece| piece.kind == PieceKind::King)
+            .count();
+        let white = self.count_by_color(Color::White);
+        let black = self.count_by_color(Color::Black);
+        only_minor_pieces && white + black <= kings + 1
+    }
+    #[doc = "returns the piece counts, material balance, turn and finish_game result of the board,
+    counting the pieces in a single pass."]
+    pub fn summary(&self) -> BoardSummary {
+        let mut summary = BoardSummary {
+            white_pieces: 0,
+            black_pieces: 0,
+            material_balance: 0,
+            turn: self.turn,
+            result: self.finish_game(),
+        };
+        for piece in self.pieces() {
+            let value = piece.kind.value() as i32;
+            match piece.color() {
+                Color::White => {
+                    summary.white_pieces += 1;
+                    summary.material_balance += value;
+                }
+                Color::Black => {
+                    summary.black_pieces += 1;
+                    summary.material_balance -= value;
+                }
+            }
+        }
+        summary
+    }
+    #[doc = "returns the material of White minus the material of Black, counted with PieceKind::value."]
+    pub fn material_balance(&self) -> i32 {
+        self.pieces()
+            .map(|piece| match piece.color() {
+                Color::White => piece.kind.value() as i32,
+                Color::Black => -(piece.kind.value() as i32),
+            })
+            .sum()
+    }
+    #[doc = "returns the material balance where every piece also earns a positional bonus, pieces near the
+    center counting slightly more. Positive values favor White."]
+    pub fn weighted_balance(&self) -> f32 {
+        self.pieces()
+            .map(|piece| {
+                let (x, y) = piece.get_position();
+                let bonus = POSITIONAL_BONUS[x * 8 / self.size][y * 8 / self.size];
+                let score = piece.kind.value() as f32 + bonus;
+                match piece.color() {
+                    Color::White => score,
+                    Color::Black => -score,
+                }
+            })
+            .sum()
+    }
+    #[doc = "returns a boolean indicating whether the board matches a pattern written like a board file,
+    where '*' matches any square. Every other square must hold exactly the given piece, or be empty for '_' and '.'.
+    A pattern with the wrong dimensions or an invalid character never matches."]
+    pub fn matches_pattern(&self, pattern: &str) -> bool {
+        let rows = pattern
+            .lines()
+            .map(|line| line.split_whitespace().collect::<String>())
+            .collect::<Vec<String>>();
+        rows.len() == self.size
+            && rows.iter().enumerate().all(|(x, row)| {
+                row.chars().count() == self.size
+                    && row.chars().enumerate().all(|(y, c)| {
+                        c == '*'
+                            || Piece::from_char(c, (x, y))
+                                .is_ok_and(|piece| piece == self.piece_at((x, y)))
+                    })
+            })
+    }
+    #[doc = "returns every square whose content differs between this board and another one, in row-major order."]
+    pub fn diff(&self, other: &Board) -> Vec<SquareChange> {
+        let size = self.size.max(other.size);
+        (0..size)
+            .flat_map(|x| (0..size).map(move |y| (x, y)))
+            .filter_map(|position| {
+                let before = self.piece_at(position);
+                let after = other.piece_at(position);
+                (before != after).then_some(SquareChange {
+                    position,
+                    before,
+                    after,
+                })
+            })
+            .collect()
+    }
+
+    #[doc = "returns a Zobrist style hash of the position, combining every piece and the side to move.
+    Boards that compare equal share the same key, and the move history plays no part in it."]
+    pub fn position_key(&self) -> u64 {
+        let key = self.pieces().fold(0, |key, piece| key ^ zobrist_key(piece));
+        match self.turn {
+            Color::White => key,
+            Color::Black => key ^ BLACK_TO_MOVE_KEY,
+        }
+    }
+
+    #[doc = "returns the algebraic name of a position, such as a8 for (0, 0) on an 8x8 board."]
+    pub fn square_name(&self, position: (usize, usize)) -> String {
+        let (x, y) = position;
+        format!("{}{}", (b'a' + y as u8) as char, self.size - x)
+    }
+    #[doc = "returns the position named by an algebraic square such as e4, if it is on the board."]
+    pub fn parse_square(&self, name: &str) -> Option<(usize, usize)> {
+        let mut chars = name.chars();
+        let file = chars.next().filter(|c| c.is_ascii_lowercase())?;
+        let rank = chars.as_str().parse::<usize>().ok()?;
+        let y = (file as u8 - b'a') as usize;
+        if rank == 0 || rank > self.size {
+            return None;
+        }
+        let position = (self.size - rank, y);
+        self.is_valid_position(position).then_some(position)
+    }
+    #[doc = "returns a piece described as its color's possessive, its kind and its algebraic square, such as \"White's rook on a1\"."]
+    fn describe(&self, piece: &Piece) -> String {
+        format!(
+            "{:?}'s {} on {}",
+            piece.color(),
+            format!("{:?}", piece.kind).to_lowercase(),
+            self.square_name(piece.get_position())
+        )
+    }
+    #[doc = "returns a sentence explaining the result of side_to_move, naming the pieces involved and their squares,
+    such as \"White's bishop on c2 can capture Black's pawn on g6, so White wins.\""]
+    pub fn explain(&self) -> String {
+        let piece_of = |color| {
+            self.get_piece_position_based_on_turn(color)
+                .and_then(|position| self.piece_at(position))
+        };
+        let (Some(white), Some(black)) = (piece_of(Color::White), piece_of(Color::Black)) else {
+            return String::from("There isn't a piece of each color, so nobody wins.");
+        };
+        let (white, black) = (self.describe(&white), self.describe(&black));
+        match self.side_to_move() {
+            Some(Winner::White) => format!("{} can capture {}, so White wins.", white, black),
+            Some(Winner::Black) => format!("{} can capture {}, so Black wins.", black, white),
+            Some(Winner::Draw) => format!(
+                "{} and {} can capture each other, so it's a draw.",
+                white, black
+            ),
+            None => format!(
+                "Neither {} nor {} can capture the other, so both lose.",
+                white, black
+            ),
+        }
+    }
+    #[doc = "returns the pieces as comma separated records for spreadsheets, a color,kind,row,col header
+    followed by one line per piece in row-major order, with lowercase color and kind names.
+    Boards that aren't 8x8 start with a size header, as board files do."]
+    pub fn to_records(&self) -> String {
+        let mut out = String::new();
+        if self.size != 8 {
+            out.push_str(&format!("size: {}\n", self.size));
+        }
+        out.push_str("color,kind,row,col\n");
+        for piece in self.sorted_pieces() {
+            let (row, col) = piece.get_position();
+            out.push_str(&format!(
+                "{},{},{},{}\n",
+                format!("{:?}", piece.color()).to_lowercase(),
+                format!("{:?}", piece.kind).to_lowercase(),
+                row,
+                col
+            ));
+        }
+        out
+    }
+    #[doc = "returns the piece placement field of the Forsyth-Edwards Notation, one rank per row
+    separated by slashes, with runs of empty squares written as digits."]
+    pub fn fen_placement(&self) -> String {
+        let mut ranks = Vec::new();
+        for x in 0..self.size {
+            let mut rank = String::new();
+            let mut empty = 0;
+            for y in 0..self.size {
+                match self.piece_at((x, y)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            rank.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        rank.push(piece.fen_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                rank.push_str(&empty.to_string());
+            }
+            ranks.push(rank);
+        }
+        ranks.join("/")
+    }
+    #[doc = "returns the side to move as a single character, 'w' for White and 'b' for Black, as in FEN."]
+    pub fn turn_char(&self) -> char {
+        match self.turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        }
+    }
+    #[doc = "returns only the first two fields of the FEN of the board, the piece placement and the side to move,
+    such as \"8/8/3q4/8/8/6R1/8/8 w\". Unlike to_fen it doesn't depend on the history or castling rights."]
+    pub fn minimal_fen(&self) -> String {
+        format!("{} {}", self.fen_placement(), self.turn_char())
+    }
+    #[doc = "returns the board in Forsyth-Edwards Notation. Castling rights are only listed while the king
+    and rook are on their starting squares, the halfmove clock is not tracked and the fullmove number
+    is counted from the moves in the history."]
+    pub fn to_fen(&self) -> String {
+        let mut castling = String::new();
+        for (color, kingside, c) in [
+            (Color::White, true, 'K'),
+            (Color::White, false, 'Q'),
+            (Color::Black, true, 'k'),
+            (Color::Black, false, 'q'),
+        ] {
+            if self.can_still_castle(color, kingside) {
+                castling.push(c);
+            }
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+        format!(
+            "{} {} {} - 0 {}",
+            self.fen_placement(),
+            self.turn_char(),
+            castling,
+            1 + self.history.len() / 2
+        )
+    }
+    #[doc = "returns whether a color keeps a castling right with its king and rook on their starting squares.
+    Horizontal boards can't castle, so they never do."]
+    fn can_still_castle(&self, color: Color, kingside: bool) -> bool {
+        if self.orientation != Orientation::Vertical {
+            return false;
+        }
+        let row = self.home_row(color);
+        let rook_column = if kingside { self.size - 1 } else { 0 };
+        let is_home = |position, kind| {
+            self.piece_at(position)
+                .is_some_and(|piece: Piece| piece.kind == kind && piece.color() == color)
+        };
+        self.castling.allows(color, kingside)
+            && is_home((row, CASTLING_KING_COLUMN), PieceKind::King)
+            && is_home((row, rook_column), PieceKind::Rook)
+    }
+
+    #[doc = "returns the winner of the game, if there is one.
+    A board without a piece of each color has no winner. Captures are checked with Board::can_capture,
+    so a piece standing between the two pieces blocks the capture."]
+    pub fn side_to_move(&self) -> Option<Winner> {
+        let curr_piece_position = self.get_piece_position_based_on_turn(self.curr_turn())?;
+        let next_piece_position = self.get_piece_position_based_on_turn(self.get_next_turn())?;
+        let curr_piece = self.piece_at(curr_piece_position)?;
+        let next_piece = self.piece_at(next_piece_position)?;
+        let current_turn_has_capture = self.can_capture(curr_piece_position, next_piece_position);
+        let next_turn_has_capture = self.can_capture(next_piece_position, curr_piece_position);
+        match (current_turn_has_capture, next_turn_has_capture) {
+            (true, false) => Some(Self::winner_for(&curr_piece)),
+            (false, true) => Some(Self::winner_for(&next_piece)),
+            (true, true) => Some(Winner::Draw),
+            _ => None,
+        }
+    }
+    #[doc = "returns a boolean indicating whether the game is over, either because side_to_move has a result
+    or because neither side has enough material left to checkmate."]
+    pub fn is_game_over(&self) -> bool {
+        self.side_to_move().is_some() || self.insufficient_material()
+    }
+    #[doc = "returns the score of the game from White's perspective: 1.0 when White wins, 0.0 when Black wins
+    and 0.5 for a draw, or None when there is no result."]
+    pub fn score(&self) -> Option<f32> {
+        self.side_to_move().map(|winner| match winner {
+            Winner::White => 1.0,
+            Winner::Black => 0.0,
+            Winner::Draw => 0.5,
+        })
+    }
+    #[doc = "returns the Winner matching the color of a given piece."]
+    fn winner_for(piece: &Piece) -> Winner {
+        if piece.is_white() {
+            Winner::White
+        } else {
+            Winner::Black
+        }
+    }
+    #[doc = "returns a compact JSON summary of the result, such as {\"result\":\"N\",\"winner\":\"Black\"}.
+    The winner is null when there is no capture."]
+    pub fn to_json(&self) -> String {
+        let winner = match self.side_to_move() {
+            Some(Winner::White) => "\"White\"",
+            Some(Winner::Black) => "\"Black\"",
+            Some(Winner::Draw) => "\"Draw\"",
+            None => "null",
+        };
+        format!(
+            "{{\"result\":\"{}\",\"winner\":{}}}",
+            self.finish_game(),
+            winner
+        )
+    }
+    #[doc = "returns the character representation of the winner of the game, one of RESULT_WHITE,
+    RESULT_BLACK, RESULT_DRAW or RESULT_NONE."]
+    pub fn finish_game(&self) -> char {
+        self.side_to_move()
+            .map(|color| match color {
+                Winner::White => Self::RESULT_WHITE,
+                Winner::Black => Self::RESULT_BLACK,
+                Winner::Draw => Self::RESULT_DRAW,
+            })
+            .unwrap_or(Self::RESULT_NONE)
+    }
+}
+
+#[doc = "A builder to construct boards fluently, placing pieces and choosing the turn, size, orientation and range."]
+#[derive(Debug)]
+pub struct BoardBuilder {
+    size: usize,
+    turn: Color,
+    orientation: Orientation,
+    max_range: Option<usize>,
+    pieces: Vec<Piece>,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardBuilder {
+    #[doc = "returns a builder for an empty 8x8 board with the White player's turn."]
+    pub fn new() -> Self {
+        BoardBuilder {
+            size: 8,
+            turn: Color::White,
+            orientation: Orientation::default(),
+            max_range: None,
+            pieces: Vec::new(),
+        }
+    }
+    #[doc = "adds a piece of a given color and kind at a given position."]
+    pub fn piece(mut self, color: Color, kind: PieceKind, position: (usize, usize)) -> Self {
+        self.pieces.push(Piece {
+            color,
+            kind,
+            position,
+        });
+        self
+    }
+    #[doc = "sets the turn of the board."]
+    pub fn turn(mut self, turn: Color) -> Self {
+        self.turn = turn;
+        self
+    }
+    #[doc = "sets the number of rows and columns of the board."]
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+    #[doc = "sets the direction pawns advance in on the board."]
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+    #[doc = "limits rooks, bishops and queens on the board to reach at most max_range squares."]
+    pub fn max_range(mut self, max_range: usize) -> Self {
+        self.max_range = Some(max_range);
+        self
+    }
+    #[doc = "builds the board. If the size is invalid, or a piece is out of the board or on an occupied position, it returns an error."]
+    pub fn build(self) -> Result<Board, BoardError> {
+        let mut board = Board::with_size(self.size)?;
+        board.set_turn(self.turn);
+        board.set_orientation(self.orientation);
+        board.set_max_range(self.max_range);
+        for piece in self.pieces {
+            board.place_piece(piece)?;
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_place_piece() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        let position = (0, 0);
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::King,
+            position,
+        })?;
+        let piece = board.piece_at(position);
+        assert_eq!(
+            piece,
+            Some(Piece {
+                color: Color::White,
+                kind: PieceKind::King,
+                position,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_piece_ref() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::Black, PieceKind::Knight, (3, 4))
+            .build()?;
+        let piece = board.piece_ref((3, 4)).unwrap();
+        let stored = board.squares.get(&(3, 4)).unwrap().as_ref().unwrap();
+        assert!(std::ptr::eq(piece, stored));
+        assert_eq!(Some(*piece), board.piece_at((3, 4)));
+        assert_eq!(board.piece_ref((0, 0)), None);
+        assert_eq!(board.piece_ref((9, 9)), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_are_eq() {
+        let mut board1 = Board {
+            squares: HashMap::new(),
+            turn: Color::White,
+            history: Vec::new(),
+            size: 8,
+            castling: CastlingRights::default(),
+            orientation: Orientation::default(),
+            max_range: None,
+        };
+        let mut board2 = Board {
+            squares: HashMap::new(),
+            turn: Color::White,
+            history: Vec::new(),
+            size: 8,
+            castling: CastlingRights::default(),
+            orientation: Orientation::default(),
+            max_range: None,
+        };
+        board1
+            .place_piece(Piece {
+                color: Color::White,
+                kind: PieceKind::King,
+                position: (0, 0),
+            })
+            .unwrap();
+        board1
+            .place_piece(Piece {
+                color: Color::Black,
+                kind: PieceKind::King,
+                position: (1, 1),
+            })
+            .unwrap();
+        board2
+            .place_piece(Piece {
+                color: Color::White,
+                kind: PieceKind::King,
+                position: (0, 0),
+            })
+            .unwrap();
+        board2
+            .place_piece(Piece {
+                color: Color::Black,
+                kind: PieceKind::King,
+                position: (1, 1),
+            })
+            .unwrap();
+        assert_eq!(board1, board2);
+    }
+
+    #[test]
+    fn test_boards_of_different_sizes_are_not_equal() -> Result<(), BoardError> {
+        let small = Board::builder()
+            .size(6)
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .build()?;
+        let large = Board::builder()
+            .size(8)
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .build()?;
+        assert_ne!(small, large);
+        assert_ne!(Board::with_size(6)?, Board::with_size(8)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_board_eq_ignores_stored_empty_squares() {
+        let king = Piece {
+            color: Color::White,
+            kind: PieceKind::King,
+            position: (4, 4),
+        };
+        let mut sparse = Board {
+            squares: HashMap::new(),
+            turn: Color::White,
+            history: Vec::new(),
+            size: 8,
+            castling: CastlingRights::default(),
+            orientation: Orientation::default(),
+            max_range: None,
+        };
+        let mut dense = Board::default_board();
+        sparse.place_piece(king).unwrap();
+        dense.place_piece(king).unwrap();
+        assert_eq!(sparse, dense);
+
+        dense.next_turn();
+        assert_ne!(sparse, dense);
+    }
+
+    #[test]
+    fn test_place_piece_invalid_position() {
+        let mut board = Board::default_board();
+        let position = (9, 9);
+        let result = board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::King,
+            position,
+        });
+        assert_eq!(result, Err(BoardError::InvalidPosition));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid position");
+    }
+
+    #[test]
     fn test_place_piece_occupied_position() {
         let mut board = Board::default_board();
         let position = (0, 0);
@@ -254,18 +1665,834 @@ mod tests {
             position,
         });
 
-        assert_eq!(result, Err(BoardError::PositionOccupied));
-        assert_eq!(result.unwrap_err().to_string(), "Position occupied");
+        assert_eq!(result, Err(BoardError::PositionOccupied));
+        assert_eq!(result.unwrap_err().to_string(), "Position occupied");
+    }
+
+    #[test]
+    fn test_next_turn() {
+        let mut board = Board::default_board();
+        assert_eq!(board.curr_turn(), Color::White);
+        board.next_turn();
+        assert_eq!(board.curr_turn(), Color::Black);
+        board.next_turn();
+        assert_eq!(board.curr_turn(), Color::White);
+    }
+
+    #[test]
+    fn test_empty_board_is_empty_and_not_full() {
+        let board = Board::default_board();
+        assert!(board.is_empty());
+        assert!(!board.is_full());
+    }
+
+    #[test]
+    fn test_populated_board_is_full_and_not_empty() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        for x in 0..8 {
+            for y in 0..8 {
+                board.place_piece(Piece {
+                    color: Color::White,
+                    kind: PieceKind::Pawn,
+                    position: (x, y),
+                })?;
+            }
+        }
+        assert!(board.is_full());
+        assert!(!board.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_can_capture_rejects_friendly_pieces() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::White, PieceKind::Pawn, (5, 2))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        let rook = board.piece_at((5, 6)).unwrap();
+        assert!(rook.can_capture((5, 2)));
+        assert!(!board.can_capture((5, 6), (5, 2)));
+        assert!(board.can_capture((5, 6), (2, 6)));
+        assert!(!board.can_capture((5, 6), (4, 6)));
+        assert!(!board.can_capture((0, 0), (2, 6)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_capture_in_one() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .turn(Color::Black)
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        assert!(board.capture_in_one((5, 6), (2, 6)));
+        assert!(board.capture_in_one((2, 6), (5, 6)));
+
+        let blocked = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Pawn, (4, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        assert!(!blocked.capture_in_one((5, 6), (2, 6)));
+        assert!(blocked.capture_in_one((5, 6), (4, 6)));
+        assert!(!blocked.capture_in_one((0, 0), (2, 6)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pawn_captures_follow_the_orientation() -> Result<(), BoardError> {
+        let builder = || {
+            Board::builder()
+                .piece(Color::White, PieceKind::Pawn, (4, 4))
+                .piece(Color::Black, PieceKind::Rook, (3, 5))
+                .piece(Color::Black, PieceKind::Rook, (5, 5))
+                .piece(Color::Black, PieceKind::Rook, (3, 3))
+        };
+
+        let horizontal = builder().build()?;
+        assert_eq!(horizontal.orientation(), Orientation::Horizontal);
+        assert!(horizontal.can_capture((4, 4), (3, 5)));
+        assert!(horizontal.can_capture((4, 4), (5, 5)));
+        assert!(!horizontal.can_capture((4, 4), (3, 3)));
+
+        let vertical = builder().orientation(Orientation::Vertical).build()?;
+        assert!(vertical.can_capture((4, 4), (3, 5)));
+        assert!(vertical.can_capture((4, 4), (3, 3)));
+        assert!(!vertical.can_capture((4, 4), (5, 5)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_vertical_pawns_advance_and_promote_along_rows() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::Pawn, (1, 0))
+            .piece(Color::Black, PieceKind::Pawn, (6, 7))
+            .build()?;
+        assert_eq!(
+            board.apply_move_mut((1, 0), (1, 1)),
+            Err(BoardError::IllegalMove)
+        );
+        board.apply_move_mut((1, 0), (0, 0))?;
+        assert_eq!(
+            board.piece_at((0, 0)).map(|piece| piece.kind),
+            Some(PieceKind::Queen)
+        );
+        board.apply_move_mut((6, 7), (7, 7))?;
+        assert_eq!(
+            board.piece_at((7, 7)).map(|piece| piece.kind),
+            Some(PieceKind::Queen)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_king_position() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::Black, PieceKind::Queen, (0, 4))
+            .piece(Color::Black, PieceKind::King, (2, 3))
+            .piece(Color::White, PieceKind::Rook, (6, 1))
+            .build()?;
+        assert_eq!(board.king_position(Color::Black), Some((2, 3)));
+        assert_eq!(board.king_position(Color::White), None);
+        assert_eq!(Board::default_board().king_position(Color::Black), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_by_color() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::Black, PieceKind::King, (0, 4))
+            .build()?;
+        assert_eq!(board.count_by_color(Color::White), 2);
+        assert_eq!(board.count_by_color(Color::Black), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insufficient_material() -> Result<(), BoardError> {
+        let kings = || {
+            Board::builder()
+                .piece(Color::White, PieceKind::King, (7, 4))
+                .piece(Color::Black, PieceKind::King, (0, 4))
+        };
+        assert!(kings().build()?.insufficient_material());
+        assert!(kings()
+            .piece(Color::Black, PieceKind::Knight, (0, 1))
+            .build()?
+            .insufficient_material());
+        assert!(!kings()
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .build()?
+            .insufficient_material());
+        assert!(!kings()
+            .piece(Color::White, PieceKind::Pawn, (6, 0))
+            .build()?
+            .insufficient_material());
+        assert!(!kings()
+            .piece(Color::White, PieceKind::Bishop, (7, 2))
+            .piece(Color::White, PieceKind::Knight, (7, 1))
+            .build()?
+            .insufficient_material());
+        Ok(())
+    }
+
+    #[test]
+    fn test_material_balance() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Queen, (7, 3))
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::Black, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::Pawn, (1, 0))
+            .build()?;
+        assert_eq!(board.material_balance(), 3);
+        assert_eq!(Board::default_board().material_balance(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted_balance_favors_central_pieces() -> Result<(), BoardError> {
+        let central = Board::builder()
+            .piece(Color::White, PieceKind::Queen, (3, 3))
+            .build()?;
+        let cornered = Board::builder()
+            .piece(Color::White, PieceKind::Queen, (0, 0))
+            .build()?;
+        assert!(central.weighted_balance() > cornered.weighted_balance());
+        assert_eq!(cornered.weighted_balance(), 9.0);
+
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Queen, (0, 7))
+            .piece(Color::Black, PieceKind::Queen, (4, 4))
+            .build()?;
+        assert_eq!(board.material_balance(), 0);
+        assert!(board.weighted_balance() < 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pseudo_legal_moves_stop_at_blockers() -> Result<(), BoardError> {
+        let builder = |color| {
+            Board::builder()
+                .size(4)
+                .piece(Color::White, PieceKind::Rook, (3, 0))
+                .piece(color, PieceKind::Pawn, (1, 0))
+                .piece(Color::White, PieceKind::King, (3, 2))
+        };
+        let friendly = builder(Color::White).build()?;
+        assert_eq!(friendly.pseudo_legal_moves((3, 0)), vec![(2, 0), (3, 1)]);
+        let enemy = builder(Color::Black).build()?;
+        assert_eq!(
+            enemy.pseudo_legal_moves((3, 0)),
+            vec![(1, 0), (2, 0), (3, 1)]
+        );
+        assert!(enemy.pseudo_legal_moves((0, 0)).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_perft() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(4)
+            .piece(Color::White, PieceKind::King, (3, 0))
+            .piece(Color::Black, PieceKind::King, (0, 3))
+            .build()?;
+        assert_eq!(board.perft(0), 1);
+        assert_eq!(board.perft(1), board.legal_moves().len() as u64);
+        assert_eq!(board.perft(1), 3);
+        assert_eq!(board.perft(2), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_replay() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::Black, PieceKind::Rook, (0, 7))
+            .build()?;
+        let replayed = board.replay(&[((7, 0), (3, 0)), ((0, 7), (0, 2)), ((3, 0), (3, 5))])?;
+        let expected = Board::builder()
+            .turn(Color::Black)
+            .piece(Color::White, PieceKind::Rook, (3, 5))
+            .piece(Color::Black, PieceKind::Rook, (0, 2))
+            .build()?;
+        assert_eq!(replayed, expected);
+        assert_eq!(replayed.history().len(), 3);
+
+        let result = board.replay(&[((7, 0), (3, 0)), ((3, 0), (3, 5))]);
+        assert_eq!(
+            result,
+            Err(BoardError::InMove {
+                index: 1,
+                error: Box::new(BoardError::NotYourTurn),
+            })
+        );
+        assert_eq!(result.unwrap_err().to_string(), "Move 1: Not your turn");
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_move_reports_the_capture() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        let result = board.apply_move((5, 6), (2, 6))?;
+        assert_eq!(
+            result.captured,
+            Some(Piece {
+                color: Color::Black,
+                kind: PieceKind::Queen,
+                position: (2, 6),
+            })
+        );
+        assert_eq!(
+            result.board.piece_at((2, 6)).map(|p| p.kind),
+            Some(PieceKind::Rook)
+        );
+        assert_eq!(board.apply_move((5, 6), (4, 6))?.captured, None);
+
+        let captured = board.apply_move_mut((5, 6), (2, 6))?;
+        assert_eq!(captured.map(|piece| piece.kind), Some(PieceKind::Queen));
+        assert_eq!(board, result.board);
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_move_uci() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        let moved = board.apply_move_uci("g3g6")?;
+        assert_eq!(moved, board.apply_move((5, 6), (2, 6))?.board);
+        assert_eq!(board.apply_move_uci("g3"), Err(BoardError::InvalidUci));
+        assert_eq!(board.apply_move_uci("g3g9"), Err(BoardError::InvalidUci));
+        assert_eq!(board.apply_move_uci("g3g6x"), Err(BoardError::InvalidUci));
+        assert_eq!(board.apply_move_uci("3g6g"), Err(BoardError::InvalidUci));
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_move_uci_with_promotion() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::Pawn, (1, 4))
+            .build()?;
+        let queen = board.apply_move_uci("e7e8q")?;
+        assert_eq!(
+            queen.piece_at((0, 4)).map(|piece| piece.kind),
+            Some(PieceKind::Queen)
+        );
+        let knight = board.apply_move_uci("e7e8n")?;
+        assert_eq!(
+            knight.piece_at((0, 4)).map(|piece| piece.kind),
+            Some(PieceKind::Knight)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_heatmap() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(4)
+            .piece(Color::White, PieceKind::Rook, (1, 2))
+            .build()?;
+        assert_eq!(
+            board.heatmap(Color::White),
+            vec![
+                vec![0, 0, 1, 0],
+                vec![1, 1, 0, 1],
+                vec![0, 0, 1, 0],
+                vec![0, 0, 1, 0],
+            ]
+        );
+        assert!(board
+            .heatmap(Color::Black)
+            .iter()
+            .all(|row| row.iter().all(|&count| count == 0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pawns_do_not_attack_the_square_in_front() -> Result<(), BoardError> {
+        for orientation in [Orientation::Horizontal, Orientation::Vertical] {
+            let board = Board::builder()
+                .orientation(orientation)
+                .piece(Color::White, PieceKind::Pawn, (4, 4))
+                .build()?;
+            let (dx, dy) = orientation.pawn_direction(Color::White);
+            let front = ((4 + dx) as usize, (4 + dy) as usize);
+            assert!(board.pseudo_legal_moves((4, 4)).contains(&front));
+            assert!(!board.is_square_attacked(front, Color::White));
+            assert!(board.attackers_of(front, Color::White).is_empty());
+            assert_eq!(board.heatmap(Color::White)[front.0][front.1], 0);
+
+            let diagonal = match orientation {
+                Orientation::Horizontal => (3, 5),
+                Orientation::Vertical => (3, 3),
+            };
+            assert!(board.is_square_attacked(diagonal, Color::White));
+            assert_eq!(board.attackers_of(diagonal, Color::White).len(), 1);
+            assert_eq!(board.heatmap(Color::White)[diagonal.0][diagonal.1], 1);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_checkers_in_double_check() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::Black, PieceKind::Rook, (0, 4))
+            .piece(Color::Black, PieceKind::Bishop, (4, 1))
+            .piece(Color::Black, PieceKind::Knight, (0, 0))
+            .build()?;
+        let checkers = board.checkers(Color::White);
+        assert_eq!(checkers.len(), 2);
+        assert_eq!(checkers[0].get_position(), (0, 4));
+        assert_eq!(checkers[1].get_position(), (4, 1));
+        assert!(board.checkers(Color::Black).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_position() {
+        let board = Board::default_board();
+        assert!(board.is_valid_position((0, 0)));
+        assert!(board.is_valid_position((7, 7)));
+        assert!(!board.is_valid_position((8, 0)));
+        assert!(!board.is_valid_position((0, 8)));
+
+        let board = Board::with_size(10).unwrap();
+        assert!(board.is_valid_position((9, 9)));
+        assert!(!board.is_valid_position((10, 3)));
+        let board = Board::with_size(4).unwrap();
+        assert!(board.is_valid_position((3, 0)));
+        assert!(!board.is_valid_position((4, 4)));
+    }
+
+    #[test]
+    fn test_with_size_rejects_invalid_sizes() {
+        assert_eq!(Board::with_size(0), Err(BoardError::InvalidSize(0)));
+        assert_eq!(Board::with_size(27), Err(BoardError::InvalidSize(27)));
+        assert_eq!(Board::with_size(26).map(|board| board.size()), Ok(26));
+        assert_eq!(
+            Board::builder().size(0).build(),
+            Err(BoardError::InvalidSize(0))
+        );
+        assert_eq!(
+            BoardError::InvalidSize(0).to_string(),
+            "Invalid board size: 0"
+        );
+    }
+
+    #[test]
+    fn test_set_piece_overwrites() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .build()?;
+        let queen = Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (0, 0),
+        };
+        assert_eq!(board.place_piece(queen), Err(BoardError::PositionOccupied));
+        board.set_piece(queen)?;
+        assert_eq!(board.piece_at((0, 0)), Some(queen));
+        assert_eq!(board.pieces().count(), 1);
+        assert_eq!(
+            board.set_piece(Piece {
+                position: (0, 8),
+                ..queen
+            }),
+            Err(BoardError::InvalidPosition)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_piece() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .size(4)
+            .piece(Color::Black, PieceKind::Queen, (3, 3))
+            .build()?;
+        assert_eq!(board.remove_piece((3, 3))?.kind, PieceKind::Queen);
+        assert_eq!(board.piece_at((3, 3)), None);
+        assert!(board.is_empty());
+        assert_eq!(board.remove_piece((3, 3)), Err(BoardError::EmptySquare));
+        assert_eq!(board.remove_piece((4, 0)), Err(BoardError::InvalidPosition));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rank_and_file() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::Black, PieceKind::Queen, (2, 3))
+            .build()?;
+        let rank = board.rank(2);
+        assert_eq!(rank.len(), 8);
+        assert_eq!(rank[3].map(|piece| piece.kind), Some(PieceKind::Queen));
+        assert_eq!(rank.iter().flatten().count(), 1);
+        let file = board.file(3);
+        assert_eq!(file[2], rank[3]);
+        assert_eq!(file.iter().flatten().count(), 1);
+        assert!(board.rank(1).iter().all(Option::is_none));
+        assert!(board.rank(8).is_empty());
+        assert!(board.file(8).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_pieces_of_kind_and_color() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::Black, PieceKind::Rook, (0, 7))
+            .piece(Color::Black, PieceKind::Queen, (0, 3))
+            .build()?;
+        let rooks = board.pieces_of_kind(PieceKind::Rook);
+        assert_eq!(
+            rooks
+                .iter()
+                .map(|piece| piece.color())
+                .collect::<Vec<Color>>(),
+            vec![Color::Black, Color::White]
+        );
+        assert!(board.pieces_of_kind(PieceKind::Pawn).is_empty());
+        let blacks = board.pieces_of_color(Color::Black);
+        assert_eq!(
+            blacks
+                .iter()
+                .map(|piece| piece.kind)
+                .collect::<Vec<PieceKind>>(),
+            vec![PieceKind::Queen, PieceKind::Rook]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_pattern() -> Result<(), BoardError> {
+        let mut rows = ["* * * * * * * *"; 8];
+        rows[2] = "* * * D * * * *";
+        let pattern = rows.join("\n");
+        rows[5] = "* * * * * * _ *";
+        let empty_g3 = rows.join("\n");
+
+        let queen = || Board::builder().piece(Color::Black, PieceKind::Queen, (2, 3));
+        let alone = queen().build()?;
+        let with_rook = queen()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .build()?;
+        assert!(alone.matches_pattern(&pattern));
+        assert!(with_rook.matches_pattern(&pattern));
+        assert!(!Board::default_board().matches_pattern(&pattern));
+        assert!(alone.matches_pattern(&empty_g3));
+        assert!(!with_rook.matches_pattern(&empty_g3));
+        assert!(!alone.matches_pattern("* *\n* *\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_side_to_move_is_blocked() -> Result<(), BoardError> {
+        let builder = || {
+            Board::builder()
+                .piece(Color::White, PieceKind::Rook, (0, 0))
+                .piece(Color::Black, PieceKind::Knight, (0, 5))
+        };
+        assert_eq!(builder().build()?.finish_game(), Board::RESULT_WHITE);
+
+        let blocked = builder()
+            .piece(Color::White, PieceKind::Pawn, (0, 3))
+            .build()?;
+        assert_eq!(blocked.side_to_move(), None);
+        assert_eq!(blocked.finish_game(), Board::RESULT_NONE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_turn_char() {
+        let mut board = Board::default_board();
+        assert_eq!(board.turn_char(), 'w');
+        board.next_turn();
+        assert_eq!(board.turn_char(), 'b');
+    }
+
+    #[test]
+    fn test_as_grid() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(6)
+            .piece(Color::Black, PieceKind::Queen, (1, 2))
+            .build()?;
+        let grid = board.as_grid();
+        assert_eq!(grid.len(), 6);
+        assert!(grid.iter().all(|row| row.len() == 6));
+        assert_eq!(grid[1][2], board.piece_ref((1, 2)));
+        assert_eq!(grid[1][2].map(|piece| piece.kind), Some(PieceKind::Queen));
+        assert_eq!(grid.iter().flatten().flatten().count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_swap() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::Knight, (7, 7))
+            .build()?;
+        board.swap((0, 0), (3, 4))?;
+        assert_eq!(board.piece_at((0, 0)), None);
+        assert_eq!(
+            board.piece_at((3, 4)),
+            Some(Piece {
+                color: Color::White,
+                kind: PieceKind::Rook,
+                position: (3, 4),
+            })
+        );
+
+        board.swap((3, 4), (7, 7))?;
+        assert_eq!(
+            board.piece_at((7, 7)).map(|p| p.kind),
+            Some(PieceKind::Rook)
+        );
+        assert_eq!(
+            board.piece_at((3, 4)).map(|p| p.get_position()),
+            Some((3, 4))
+        );
+        board.swap((7, 7), (7, 7))?;
+        assert_eq!(
+            board.piece_at((7, 7)).map(|p| p.kind),
+            Some(PieceKind::Rook)
+        );
+
+        let before = board.clone();
+        assert_eq!(board.swap((7, 7), (8, 0)), Err(BoardError::InvalidPosition));
+        assert_eq!(board, before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_squares_between() {
+        assert_eq!(
+            Board::squares_between((0, 0), (0, 4)),
+            vec![(0, 1), (0, 2), (0, 3)]
+        );
+        assert_eq!(Board::squares_between((5, 2), (2, 2)), vec![(4, 2), (3, 2)]);
+        assert_eq!(
+            Board::squares_between((7, 0), (3, 4)),
+            vec![(6, 1), (5, 2), (4, 3)]
+        );
+        assert!(Board::squares_between((0, 0), (1, 2)).is_empty());
+        assert!(Board::squares_between((0, 0), (1, 1)).is_empty());
+        assert!(Board::squares_between((4, 4), (4, 4)).is_empty());
+    }
+
+    #[test]
+    fn test_explain_draw_and_no_capture() -> Result<(), BoardError> {
+        let draw = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::Black, PieceKind::Queen, (0, 0))
+            .build()?;
+        assert_eq!(
+            draw.explain(),
+            "White's rook on a1 and Black's queen on a8 can capture each other, so it's a draw."
+        );
+        let none = Board::builder()
+            .piece(Color::White, PieceKind::Knight, (7, 0))
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .build()?;
+        assert_eq!(
+            none.explain(),
+            "Neither White's knight on a1 nor Black's king on h8 can capture the other, so both lose."
+        );
+        assert_eq!(
+            Board::default_board().explain(),
+            "There isn't a piece of each color, so nobody wins."
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_empty_at() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .build()?;
+        assert!(board.is_empty_at((0, 0)));
+        assert!(!board.is_empty_at((5, 6)));
+        assert!(!board.is_empty_at((8, 0)));
+        assert!(!board.is_empty_at((0, 8)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_occupied_and_empty_squares() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 3))
+            .build()?;
+        assert_eq!(board.occupied_squares(), vec![(2, 3), (5, 6)]);
+        let empty = board.empty_squares();
+        assert_eq!(empty.first(), Some(&(0, 0)));
+        assert!(!empty.contains(&(2, 3)));
+        assert_eq!(
+            board.occupied_squares().len() + empty.len(),
+            board.size() * board.size()
+        );
+
+        let board = Board::with_size(5)?;
+        assert!(board.occupied_squares().is_empty());
+        assert_eq!(board.empty_squares().len(), 25);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip_vertical() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        })?;
+        let flipped = board.flip_vertical(false);
+        assert_eq!(flipped.piece_at((0, 0)), None);
+        assert_eq!(
+            flipped.piece_at((7, 0)),
+            Some(Piece {
+                color: Color::White,
+                kind: PieceKind::Rook,
+                position: (7, 0),
+            })
+        );
+        assert_eq!(flipped.curr_turn(), Color::White);
+
+        let swapped = board.flip_vertical(true);
+        assert_eq!(
+            swapped.piece_at((7, 0)),
+            Some(Piece {
+                color: Color::Black,
+                kind: PieceKind::Rook,
+                position: (7, 0),
+            })
+        );
+        assert_eq!(swapped.curr_turn(), Color::Black);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mirror_colors() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 3))
+            .build()?;
+        let mirrored = board.mirror_colors();
+        let expected = Board::builder()
+            .turn(Color::Black)
+            .piece(Color::Black, PieceKind::Rook, (5, 6))
+            .piece(Color::White, PieceKind::Queen, (2, 3))
+            .build()?;
+        assert_eq!(mirrored, expected);
+        assert_eq!(mirrored.mirror_colors(), board);
+        assert_eq!(
+            board.flip_vertical(false).mirror_colors(),
+            board.flip_vertical(true)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip_horizontal_and_rotate_180() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Knight,
+            position: (1, 2),
+        })?;
+        let flipped = board.flip_horizontal();
+        assert_eq!(
+            flipped.piece_at((1, 5)).map(|p| p.get_position()),
+            Some((1, 5))
+        );
+        let rotated = board.rotate_180();
+        assert_eq!(
+            rotated.piece_at((6, 5)).map(|p| p.get_position()),
+            Some((6, 5))
+        );
+        assert_eq!(rotated.rotate_180(), board);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rotate_quarter_turns() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        })?;
+        let corner = |board: &Board| board.pieces().map(|p| p.get_position()).collect::<Vec<_>>();
+
+        let once = board.rotate_cw();
+        assert_eq!(corner(&once), vec![(0, 7)]);
+        let twice = once.rotate_cw();
+        assert_eq!(corner(&twice), vec![(7, 7)]);
+        assert_eq!(twice, board.rotate_180());
+        let thrice = twice.rotate_cw();
+        assert_eq!(corner(&thrice), vec![(7, 0)]);
+        assert_eq!(thrice.rotate_cw(), board);
+
+        assert_eq!(board.rotate_ccw(), thrice);
+        assert_eq!(once.rotate_ccw(), board);
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(5)
+            .turn(Color::Black)
+            .piece(Color::White, PieceKind::King, (4, 4))
+            .build()?;
+        assert_eq!(board.size(), 5);
+        assert_eq!(board.curr_turn(), Color::Black);
+        assert_eq!(
+            board.piece_at((4, 4)).map(|p| p.kind),
+            Some(PieceKind::King)
+        );
+
+        let result = Board::builder()
+            .size(5)
+            .piece(Color::White, PieceKind::King, (5, 0))
+            .build();
+        assert_eq!(result, Err(BoardError::InvalidPosition));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(3)
+            .piece(Color::Black, PieceKind::Queen, (0, 1))
+            .piece(Color::White, PieceKind::Rook, (2, 2))
+            .build()?;
+        assert_eq!(board.to_string(), "_ D _\n_ _ _\n_ _ t\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_board_with_turn() {
+        let board = Board::default_board_with_turn(Color::Black);
+        assert_eq!(board.curr_turn(), Color::Black);
+        assert!(board.is_empty());
+        assert_eq!(board.size(), 8);
     }
 
     #[test]
-    fn test_next_turn() {
+    fn test_set_turn() {
         let mut board = Board::default_board();
-        assert_eq!(board.curr_turn(), Color::White);
-        board.next_turn();
+        board.set_turn(Color::Black);
         assert_eq!(board.curr_turn(), Color::Black);
-        board.next_turn();
-        assert_eq!(board.curr_turn(), Color::White);
     }
 
     #[test]
@@ -352,6 +2579,614 @@ mod tests {
         assert_eq!(board.side_to_move(), Some(Winner::Draw));
     }
 
+    #[test]
+    fn test_apply_moves_and_undo_to_initial_position() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        })?;
+        board.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Knight,
+            position: (5, 2),
+        })?;
+        let mut initial = Board::default_board();
+        initial.place_piece(board.piece_at((0, 0)).unwrap())?;
+        initial.place_piece(board.piece_at((5, 2)).unwrap())?;
+
+        board.apply_move_mut((0, 0), (0, 4))?;
+        board.apply_move_mut((5, 2), (3, 3))?;
+        assert_eq!(
+            board.history(),
+            &[
+                MoveRecord {
+                    from: (0, 0),
+                    to: (0, 4),
+                    captured: None,
+                    promotion: None,
+                    castling: CastlingRights::default(),
+                },
+                MoveRecord {
+                    from: (5, 2),
+                    to: (3, 3),
+                    captured: None,
+                    promotion: None,
+                    castling: CastlingRights {
+                        black_kingside: false,
+                        black_queenside: false,
+                        ..CastlingRights::default()
+                    },
+                },
+            ]
+        );
+
+        board.undo()?;
+        board.undo()?;
+        assert_eq!(board, initial);
+        assert!(board.history().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_restores_captured_piece() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        let rook = Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        };
+        let king = Piece {
+            color: Color::Black,
+            kind: PieceKind::King,
+            position: (0, 7),
+        };
+        board.place_piece(rook)?;
+        board.place_piece(king)?;
+
+        board.apply_move_mut((0, 0), (0, 7))?;
+        assert_eq!(board.history()[0].captured, Some(king));
+        assert_eq!(board.curr_turn(), Color::Black);
+
+        board.undo()?;
+        assert_eq!(board.piece_at((0, 7)), Some(king));
+        assert_eq!(board.piece_at((0, 0)), Some(rook));
+        assert_eq!(board.curr_turn(), Color::White);
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_past_the_start() {
+        let mut board = Board::default_board();
+        let result = board.undo();
+        assert_eq!(result, Err(BoardError::NothingToUndo));
+        assert_eq!(result.unwrap_err().to_string(), "Nothing to undo");
+    }
+
+    #[test]
+    fn test_apply_move_rejects_blocked_path() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        })?;
+        board.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Pawn,
+            position: (0, 3),
+        })?;
+        assert_eq!(
+            board.apply_move_mut((0, 0), (0, 5)),
+            Err(BoardError::IllegalMove)
+        );
+        assert_eq!(
+            board.apply_move_mut((0, 3), (0, 2)),
+            Err(BoardError::NotYourTurn)
+        );
+        assert_eq!(
+            board.apply_move_mut((4, 4), (4, 5)),
+            Err(BoardError::EmptySquare)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_with() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(3)
+            .piece(Color::White, PieceKind::King, (2, 0))
+            .piece(Color::Black, PieceKind::Queen, (0, 2))
+            .build()?;
+        assert_eq!(board.render_with('.', "  "), ".  .  D\n.  .  .\nr  .  .\n");
+        assert_eq!(board.render_with('_', " "), board.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_unicode() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .size(3)
+            .piece(Color::White, PieceKind::King, (2, 0))
+            .piece(Color::Black, PieceKind::Queen, (0, 2))
+            .build()?;
+        assert_eq!(board.render_unicode(), "· · ♛\n· · ·\n♔ · ·\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_pretty() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (2, 3),
+        })?;
+        let rendered = board.render_pretty();
+        let lines = rendered.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "8 | _ _ _ _ _ _ _ _");
+        assert_eq!(lines[2], "6 | _ _ _ D _ _ _ _");
+        assert_eq!(lines[7], "1 | _ _ _ _ _ _ _ _");
+        assert_eq!(lines[8], "  +----------------");
+        assert_eq!(lines[9], "    a b c d e f g h");
+        Ok(())
+    }
+
+    #[cfg(feature = "ansi")]
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[cfg(feature = "ansi")]
+    #[test]
+    fn test_render_ansi() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        board.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (0, 1),
+        })?;
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 2),
+        })?;
+        let rendered = board.render_ansi();
+        assert!(rendered.starts_with("\x1b[43m   \x1b[0m"));
+        assert!(rendered.contains("\x1b[42m\x1b[1;30m D \x1b[0m"));
+        assert!(rendered.contains("\x1b[43m\x1b[1;97m t \x1b[0m"));
+
+        let stripped = strip_ansi(&rendered);
+        let lines = stripped.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 8);
+        assert!(lines.iter().all(|line| line.len() == 24));
+        assert_eq!(&lines[0][..9], "    D  t ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_legal_moves_for_lone_king() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .piece(Color::Black, PieceKind::Rook, (1, 1))
+            .piece(Color::Black, PieceKind::Knight, (3, 3))
+            .build()?;
+        assert_eq!(board.legal_moves(), vec![((0, 0), (1, 1))]);
+
+        let defended = Board::builder()
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .piece(Color::Black, PieceKind::Rook, (1, 1))
+            .piece(Color::Black, PieceKind::Bishop, (3, 3))
+            .build()?;
+        assert!(defended.legal_moves().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_range_limits_sliding_pieces() -> Result<(), BoardError> {
+        let builder = || {
+            Board::builder()
+                .piece(Color::White, PieceKind::Rook, (0, 0))
+                .piece(Color::Black, PieceKind::Rook, (0, 5))
+                .piece(Color::Black, PieceKind::Knight, (3, 0))
+        };
+        let unlimited = builder().build()?;
+        assert_eq!(unlimited.max_range(), None);
+        assert!(unlimited.can_capture((0, 0), (0, 5)));
+
+        let limited = builder().max_range(3).build()?;
+        assert_eq!(limited.max_range(), Some(3));
+        assert!(!limited.can_capture((0, 0), (0, 5)));
+        assert!(limited.can_capture((0, 0), (3, 0)));
+        assert!(limited.apply_move((0, 0), (0, 3)).is_ok());
+        assert_eq!(
+            limited.apply_move((0, 0), (0, 4)),
+            Err(BoardError::IllegalMove)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_capture() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::Knight, (0, 5))
+            .piece(Color::White, PieceKind::Pawn, (3, 0))
+            .build()?;
+        assert!(board.is_capture((0, 0), (0, 5)));
+        assert!(!board.is_capture((0, 0), (0, 3)));
+        assert!(!board.is_capture((0, 0), (3, 0)));
+        assert!(!board.is_capture((4, 4), (0, 5)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_legal_captures() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .piece(Color::Black, PieceKind::Knight, (1, 1))
+            .piece(Color::Black, PieceKind::King, (7, 7))
+            .build()?;
+        assert_eq!(board.legal_moves().len(), 3);
+        assert_eq!(board.legal_captures(), vec![((0, 0), (1, 1))]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_legal_move() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .piece(Color::White, PieceKind::Rook, (0, 1))
+            .piece(Color::White, PieceKind::Bishop, (3, 3))
+            .piece(Color::Black, PieceKind::Rook, (0, 7))
+            .piece(Color::Black, PieceKind::Pawn, (5, 5))
+            .build()?;
+        assert!(board.is_legal_move((0, 1), (0, 5)));
+        assert!(board.is_legal_move((0, 1), (0, 7)));
+        assert!(board.is_legal_move((3, 3), (5, 5)));
+        // The rook is pinned against its king.
+        assert!(!board.is_legal_move((0, 1), (1, 1)));
+        // The pawn is in the way of the bishop.
+        assert!(!board.is_legal_move((3, 3), (6, 6)));
+        assert!(!board.is_legal_move((0, 0), (0, 1)));
+        assert!(!board.is_legal_move((0, 7), (0, 6)));
+        assert!(!board.is_legal_move((4, 4), (5, 5)));
+        assert_eq!(
+            board.piece_at((0, 1)).map(|p| p.get_position()),
+            Some((0, 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_move_rejects_self_check() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (0, 0))
+            .piece(Color::White, PieceKind::Rook, (0, 1))
+            .piece(Color::Black, PieceKind::Rook, (0, 7))
+            .build()?;
+        assert!(!board.is_in_check(Color::White));
+        assert_eq!(
+            board.apply_move((0, 1), (1, 1)),
+            Err(BoardError::KingInCheck)
+        );
+        let moved = board.apply_move((0, 1), (0, 5))?.board;
+        assert_eq!(board.piece_at((0, 5)), None);
+        assert_eq!(
+            moved.piece_at((0, 5)).map(|p| p.kind),
+            Some(PieceKind::Rook)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_capture_graph() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .piece(Color::Black, PieceKind::Bishop, (2, 2))
+            .build()?;
+        let rook = board.piece_at((0, 0)).unwrap();
+        let king = board.piece_at((0, 7)).unwrap();
+        let bishop = board.piece_at((2, 2)).unwrap();
+        assert_eq!(board.capture_graph(), vec![(rook, king), (bishop, rook)]);
+
+        let blocked = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::Knight, (0, 3))
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .build()?;
+        let knight = blocked.piece_at((0, 3)).unwrap();
+        assert_eq!(blocked.capture_graph(), vec![(rook, knight)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff() -> Result<(), BoardError> {
+        let before = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::Black, PieceKind::King, (0, 4))
+            .build()?;
+        let after = before.apply_move((7, 0), (3, 0))?.board;
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                SquareChange {
+                    position: (3, 0),
+                    before: None,
+                    after: after.piece_at((3, 0)),
+                },
+                SquareChange {
+                    position: (7, 0),
+                    before: before.piece_at((7, 0)),
+                    after: None,
+                },
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_key() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .build()?;
+        let same = Board::builder()
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .piece(Color::White, PieceKind::Rook, (0, 0))
+            .build()?;
+        assert_eq!(board, same);
+        assert_eq!(board.position_key(), same.position_key());
+
+        let relocated = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (0, 1))
+            .piece(Color::Black, PieceKind::King, (0, 7))
+            .build()?;
+        assert_ne!(board.position_key(), relocated.position_key());
+
+        let mut black_to_move = same;
+        black_to_move.next_turn();
+        assert_ne!(board.position_key(), black_to_move.position_key());
+        Ok(())
+    }
+
+    #[test]
+    fn test_side_to_move_without_both_colors() -> Result<(), BoardError> {
+        let mut board = Board::default_board();
+        assert_eq!(board.side_to_move(), None);
+        assert_eq!(board.finish_game(), 'P');
+        board.place_piece(Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (0, 0),
+        })?;
+        assert_eq!(board.side_to_move(), None);
+        assert_eq!(board.finish_game(), 'P');
+        Ok(())
+    }
+
+    #[test]
+    fn test_white_pawn_promotes_to_queen_on_last_rank() -> Result<(), BoardError> {
+        let mut board = Board::builder()
+            .piece(Color::White, PieceKind::Pawn, (3, 6))
+            .build()?;
+        board.apply_move_mut((3, 6), (3, 7))?;
+        assert_eq!(
+            board.piece_at((3, 7)).map(|p| p.kind),
+            Some(PieceKind::Queen)
+        );
+        assert_eq!(board.history()[0].promotion, Some(PieceKind::Queen));
+
+        board.undo()?;
+        assert_eq!(
+            board.piece_at((3, 6)).map(|p| p.kind),
+            Some(PieceKind::Pawn)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pawn_promotes_to_chosen_kind() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::Black, PieceKind::Pawn, (3, 1))
+            .piece(Color::White, PieceKind::Pawn, (5, 5))
+            .turn(Color::Black)
+            .build()?;
+        let promoted = board
+            .apply_move_with_promotion((3, 1), (3, 0), PieceKind::Knight)?
+            .board;
+        assert_eq!(
+            promoted.piece_at((3, 0)).map(|p| p.kind),
+            Some(PieceKind::Knight)
+        );
+        assert_eq!(
+            board.apply_move_with_promotion((3, 1), (3, 0), PieceKind::King),
+            Err(BoardError::InvalidPromotion)
+        );
+
+        let advanced = promoted
+            .apply_move_with_promotion((5, 5), (5, 6), PieceKind::Rook)?
+            .board;
+        assert_eq!(
+            advanced.piece_at((5, 6)).map(|p| p.kind),
+            Some(PieceKind::Pawn)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_square_attacked() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::Black, PieceKind::Knight, (3, 3))
+            .piece(Color::Black, PieceKind::Rook, (7, 7))
+            .piece(Color::White, PieceKind::Bishop, (0, 0))
+            .build()?;
+        assert!(board.is_square_attacked((1, 2), Color::Black));
+        assert!(!board.is_square_attacked((1, 2), Color::White));
+        assert!(board.is_square_attacked((2, 2), Color::White));
+        assert!(!board.is_square_attacked((4, 4), Color::White));
+        Ok(())
+    }
+
+    #[test]
+    fn test_center_control() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Knight, (5, 5))
+            .piece(Color::Black, PieceKind::Queen, (3, 3))
+            .build()?;
+        assert_eq!(board.center_control(Color::White), 2);
+        assert_eq!(board.center_control(Color::Black), 3);
+        assert_eq!(Board::default_board().center_control(Color::White), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kingside_castling() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 7))
+            .piece(Color::Black, PieceKind::King, (0, 4))
+            .build()?;
+        assert!(board.legal_moves().contains(&((7, 4), (7, 6))));
+
+        let mut castled = board.apply_move((7, 4), (7, 6))?.board;
+        assert_eq!(
+            castled.piece_at((7, 6)).map(|p| p.kind),
+            Some(PieceKind::King)
+        );
+        assert_eq!(
+            castled.piece_at((7, 5)).map(|p| p.kind),
+            Some(PieceKind::Rook)
+        );
+        assert_eq!(castled.piece_at((7, 7)), None);
+        assert!(!castled.castling_rights().white_kingside);
+        assert!(!castled.castling_rights().white_queenside);
+        assert!(castled.castling_rights().black_kingside);
+
+        castled.undo()?;
+        assert_eq!(castled, board);
+        assert_eq!(castled.castling_rights(), CastlingRights::default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_castling_through_attacked_square() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 7))
+            .piece(Color::Black, PieceKind::Rook, (0, 5))
+            .build()?;
+        assert_eq!(
+            board.apply_move((7, 4), (7, 6)),
+            Err(BoardError::IllegalMove)
+        );
+        assert!(!board.legal_moves().contains(&((7, 4), (7, 6))));
+
+        let blocked = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 0))
+            .piece(Color::White, PieceKind::Knight, (7, 1))
+            .build()?;
+        assert_eq!(
+            blocked.apply_move((7, 4), (7, 2)),
+            Err(BoardError::IllegalMove)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_castling_right_lost_after_rook_moves() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 7))
+            .piece(Color::Black, PieceKind::King, (0, 0))
+            .build()?;
+        let board = board
+            .apply_move((7, 7), (6, 7))?
+            .board
+            .apply_move((0, 0), (0, 1))?
+            .board
+            .apply_move((6, 7), (7, 7))?
+            .board
+            .apply_move((0, 1), (0, 0))?
+            .board;
+        assert!(!board.castling_rights().white_kingside);
+        assert_eq!(
+            board.apply_move((7, 4), (7, 6)),
+            Err(BoardError::IllegalMove)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_horizontal_boards_cannot_castle_or_play_uci() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 7))
+            .piece(Color::Black, PieceKind::King, (0, 4))
+            .build()?;
+        assert_eq!(board.orientation(), Orientation::Horizontal);
+        assert_eq!(
+            board.apply_move((7, 4), (7, 6)),
+            Err(BoardError::IllegalMove)
+        );
+        assert!(board.to_fen().contains(" w - - "));
+        assert_eq!(
+            board.apply_move_uci("e1f1"),
+            Err(BoardError::UnsupportedOrientation)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_square_names() {
+        let board = Board::default_board();
+        assert_eq!(board.square_name((0, 0)), "a8");
+        assert_eq!(board.square_name((7, 4)), "e1");
+        assert_eq!(board.parse_square("e1"), Some((7, 4)));
+        assert_eq!(board.parse_square("h8"), Some((0, 7)));
+        assert_eq!(board.parse_square("i1"), None);
+        assert_eq!(board.parse_square("a9"), None);
+        assert_eq!(board.parse_square("a0"), None);
+        assert_eq!(board.parse_square("e"), None);
+    }
+
+    #[test]
+    fn test_to_fen() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .orientation(Orientation::Vertical)
+            .piece(Color::White, PieceKind::King, (7, 4))
+            .piece(Color::White, PieceKind::Rook, (7, 7))
+            .piece(Color::Black, PieceKind::King, (0, 4))
+            .piece(Color::Black, PieceKind::Pawn, (1, 0))
+            .build()?;
+        assert_eq!(board.to_fen(), "4k3/p7/8/8/8/8/8/4K2R w K - 0 1");
+        let board = board.apply_move((7, 7), (6, 7))?.board;
+        assert_eq!(board.to_fen(), "4k3/p7/8/8/8/8/7R/4K3 b - - 0 1");
+        Ok(())
+    }
+
     #[test]
     fn test_black_wins() {
         let mut board = Board::default_board();
@@ -373,4 +3208,104 @@ mod tests {
         let winner = board.finish_game();
         assert_eq!(winner, 'N');
     }
+
+    #[test]
+    fn test_result_constants_match_finish_game() -> Result<(), BoardError> {
+        assert_eq!(Board::RESULT_WHITE, 'B');
+        assert_eq!(Board::RESULT_BLACK, 'N');
+        assert_eq!(Board::RESULT_DRAW, 'E');
+        assert_eq!(Board::RESULT_NONE, 'P');
+
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 3))
+            .build()?;
+        assert_eq!(board.finish_game(), Board::RESULT_BLACK);
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Knight, (0, 0))
+            .build()?;
+        assert_eq!(board.finish_game(), Board::RESULT_NONE);
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .piece(Color::Black, PieceKind::Queen, (2, 6))
+            .build()?;
+        assert_eq!(board.finish_game(), Board::RESULT_DRAW);
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Queen, (5, 5))
+            .piece(Color::Black, PieceKind::Knight, (0, 0))
+            .build()?;
+        assert_eq!(board.finish_game(), Board::RESULT_WHITE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone_is_independent() -> Result<(), BoardError> {
+        let board = Board::builder()
+            .piece(Color::White, PieceKind::Rook, (5, 6))
+            .build()?;
+        let mut copy = board.clone();
+        copy.place_piece(Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (2, 3),
+        })?;
+        copy.next_turn();
+        assert_eq!(board.piece_at((2, 3)), None);
+        assert_eq!(board.curr_turn(), Color::White);
+        assert_ne!(board, copy);
+
+        let mut board = board;
+        let copy = board.clone();
+        board.apply_move_mut((5, 6), (0, 6))?;
+        assert_eq!(copy.piece_at((0, 6)), None);
+        assert_eq!(
+            copy.piece_at((5, 6)).map(|piece| piece.kind),
+            Some(PieceKind::Rook)
+        );
+        assert_eq!(copy.curr_turn(), Color::White);
+        assert!(copy.history().is_empty());
+        assert_eq!(board.history().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_winner_opposite() {
+        assert_eq!(Winner::White.opposite(), Winner::Black);
+        assert_eq!(Winner::Black.opposite(), Winner::White);
+        assert_eq!(Winner::Draw.opposite(), Winner::Draw);
+    }
+
+    #[test]
+    fn test_advance_turn_if() {
+        let mut board = Board::default_board();
+        board.advance_turn_if(false);
+        assert_eq!(board.curr_turn(), Color::White);
+        board.advance_turn_if(true);
+        assert_eq!(board.curr_turn(), Color::Black);
+        board.advance_turn_if(false);
+        assert_eq!(board.curr_turn(), Color::Black);
+    }
+
+    #[test]
+    fn test_score() -> Result<(), BoardError> {
+        let score = |white: (PieceKind, (usize, usize))| -> Result<Option<f32>, BoardError> {
+            Ok(Board::builder()
+                .piece(Color::White, white.0, white.1)
+                .piece(Color::Black, PieceKind::Queen, (2, 6))
+                .build()?
+                .score())
+        };
+        assert_eq!(score((PieceKind::Rook, (5, 6)))?, Some(0.5));
+        assert_eq!(score((PieceKind::Knight, (0, 0)))?, None);
+        assert_eq!(score((PieceKind::Knight, (4, 5)))?, Some(1.0));
+        assert_eq!(score((PieceKind::Rook, (4, 4)))?, Some(0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_json() {
+        let board = Board::default_board();
+        assert_eq!(board.to_json(), "{\"result\":\"P\",\"winner\":null}");
+    }
 }
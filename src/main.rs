@@ -1,19 +1,89 @@
+use rustmate::board::Board;
 use rustmate::parser::from_path;
 use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-fn main() {
-    if let Some(filename) = env::args().nth(1) {
-        match from_path(&filename) {
+#[doc = "Parses every file and prints how many ended in each result, plus how many could not be parsed."]
+fn print_tally(filenames: &[&String]) {
+    let mut counts = [
+        (Board::RESULT_WHITE, 0),
+        (Board::RESULT_BLACK, 0),
+        (Board::RESULT_DRAW, 0),
+        (Board::RESULT_NONE, 0),
+    ];
+    let mut errors = 0;
+    for filename in filenames {
+        match from_path(filename) {
             Ok(board) => {
-                println!("{}", board.finish_game());
-            }
-            Err(err) => {
-                eprintln!("ERROR: [{}]", err);
-                std::process::exit(1);
+                let result = board.finish_game();
+                if let Some((_, count)) = counts.iter_mut().find(|(c, _)| *c == result) {
+                    *count += 1;
+                }
             }
+            Err(_) => errors += 1,
+        }
+    }
+    let tally = counts
+        .iter()
+        .map(|(c, count)| format!("{}: {}", c, count))
+        .collect::<Vec<String>>();
+    println!("{} ERROR: {}", tally.join(" "), errors);
+}
+
+#[doc = "Parses every .txt file of a directory in name order and prints each file name with its result.
+Files that cannot be read or parsed are reported on stderr and skipped."]
+fn print_directory(dir: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut paths = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+    for path in paths {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        match from_path(&path.to_string_lossy()) {
+            Ok(board) => println!("{}: {}", name, board.finish_game()),
+            Err(err) => eprintln!("{}: ERROR: [{}]", name, err),
         }
+    }
+    Ok(())
+}
+
+#[doc = "Runs the program with the given arguments, returning any error to be reported by main."]
+fn run(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let show_board = args.iter().any(|arg| arg == "--show-board");
+    let json = args.iter().any(|arg| arg == "--json");
+    let filenames = args
+        .iter()
+        .filter(|arg| !arg.starts_with("--"))
+        .collect::<Vec<&String>>();
+    if args.iter().any(|arg| arg == "--tally") {
+        print_tally(&filenames);
+        return Ok(());
+    }
+    let filename = filenames
+        .first()
+        .ok_or("Please provide a filename as an argument")?;
+    if Path::new(filename).is_dir() {
+        return print_directory(Path::new(filename));
+    }
+    let board = from_path(filename)?;
+    if show_board {
+        print!("{}", board);
+    }
+    if json {
+        println!("{}", board.to_json());
     } else {
-        eprintln!("ERROR: [Please provide a filename as an argument]");
+        println!("{}", board.finish_game());
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+    if let Err(err) = run(&args) {
+        eprintln!("ERROR: [{}]", err);
         std::process::exit(1);
     }
 }
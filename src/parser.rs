@@ -1,11 +1,25 @@
 use crate::board::{Board, BoardError};
-use crate::piece::{Piece, PieceError};
+use crate::piece::{Color, Notation, Piece, PieceError, PieceKind, EMPTY_SQUARE_CHARS};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt::Display;
 use std::fs::File;
+use std::io::Read;
 #[allow(unused_imports)]
 use std::io::Write;
-use std::io::{BufRead, BufReader};
+#[cfg(feature = "gzip")]
+use std::io::{Seek, SeekFrom};
+use std::str::FromStr;
+
+#[cfg(feature = "gzip")]
+#[doc = "The first two bytes of every gzip stream."]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[doc = "An invalid piece skipped by from_string_lossy, as its (row, col, char)."]
+pub type ParseProblem = (usize, usize, char);
+
+#[doc = "The default maximum size in bytes of a board file. A valid 8x8 board takes a little over a hundred bytes."]
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 4096;
 
 #[doc = "
 ParserError is an enum that represents possible errors that can occur while parsing a file that describes a chess game.
@@ -14,11 +28,26 @@ Possible error types are:
 
 - FileNotExists: indicates that the file does not exist.
 - FileIsEmpty: indicates that the file is empty.
+- FileTooLarge: indicates that the file exceeds the maximum allowed size.
 - InvalidBoardSize: indicates that the board size specified in the file is not valid.
 - NotEnoughTokens: indicates that there are not enough tokens in a line of the file.
 - InvalidPosition: indicates that a position specified in the file is not valid.
 - PositionOccupied: indicates that a position specified in the file is already occupied for a piece.
 - InvalidPiece: indicates that a piece specified in the file is not valid.
+- RaggedBoard: indicates that a row has a different width than the rows before it.
+- Board: indicates any other error reported by the board.
+- InBlock: indicates an error in one of the boards of a file holding several of them.
+- TooManyPieces: indicates that a strictly parsed board holds more than one piece of a color,
+  or that there are more pieces than squares on the board.
+- NotEnoughPieces: indicates that a strictly parsed board lacks a piece of a color.
+- At: indicates an error found at a given row and column of the board.
+- InvalidSeparator: indicates that a row separates its squares with something other than whitespace.
+- InvalidRankLabel: indicates that the rank label of a row is missing or doesn't match its rank.
+- InvalidRecord: indicates a malformed line of a piece records export.
+- NonAscii: indicates a byte outside of ASCII at a given row and column of the file.
+- TrailingContent: indicates content after the last row of an otherwise complete board.
+- PieceLimitExceeded: indicates more pieces of a color and kind than the PieceLimits in use allow.
+- InvalidGzip: indicates a gzip compressed file that cannot be decompressed.
 
 This enum derives from Debug and PartialEq traits.
 "]
@@ -26,11 +55,43 @@ This enum derives from Debug and PartialEq traits.
 pub enum ParserError {
     FileNotExists,
     FileIsEmpty,
+    FileTooLarge,
     InvalidBoardSize,
     NotEnoughTokens,
     InvalidPosition,
     PositionOccupied,
     InvalidPiece(char),
+    RaggedBoard {
+        expected: usize,
+        found: usize,
+        row: usize,
+    },
+    Board(BoardError),
+    InBlock {
+        index: usize,
+        error: Box<ParserError>,
+    },
+    TooManyPieces,
+    NotEnoughPieces,
+    At {
+        row: usize,
+        col: usize,
+        error: Box<ParserError>,
+    },
+    InvalidSeparator(char),
+    InvalidRankLabel(usize),
+    InvalidRecord {
+        line: usize,
+        record: String,
+    },
+    NonAscii(u8, usize, usize),
+    TrailingContent(usize),
+    PieceLimitExceeded {
+        color: Color,
+        kind: PieceKind,
+        limit: usize,
+    },
+    InvalidGzip,
 }
 
 impl From<BoardError> for ParserError {
@@ -38,6 +99,8 @@ impl From<BoardError> for ParserError {
         match err {
             BoardError::InvalidPosition => ParserError::InvalidPosition,
             BoardError::PositionOccupied => ParserError::PositionOccupied,
+            BoardError::InvalidSize(_) => ParserError::InvalidBoardSize,
+            err => ParserError::Board(err),
         }
     }
 }
@@ -57,18 +120,82 @@ impl Display for ParserError {
         match self {
             ParserError::FileNotExists => write!(f, "File not exists"),
             ParserError::FileIsEmpty => write!(f, "File is empty"),
+            ParserError::FileTooLarge => write!(f, "File too large"),
             ParserError::InvalidBoardSize => write!(f, "Invalid board size"),
             ParserError::NotEnoughTokens => write!(f, "Not enough tokens"),
             ParserError::InvalidPosition => write!(f, "{}", BoardError::InvalidPosition),
             ParserError::PositionOccupied => write!(f, "{}", BoardError::PositionOccupied),
             ParserError::InvalidPiece(c) => write!(f, "{}", PieceError::InvalidPieceKind(*c)),
+            ParserError::RaggedBoard {
+                expected,
+                found,
+                row,
+            } => write!(
+                f,
+                "Ragged board: row {} has {} columns, expected {}",
+                row, found, expected
+            ),
+            ParserError::Board(err) => write!(f, "{}", err),
+            ParserError::InBlock { index, error } => write!(f, "Block {}: {}", index, error),
+            ParserError::TooManyPieces => write!(f, "Too many pieces"),
+            ParserError::NotEnoughPieces => write!(f, "Not enough pieces"),
+            ParserError::At { row, col, error } => {
+                write!(f, "{} at row {}, column {}", error, row, col)
+            }
+            ParserError::InvalidSeparator(c) => write!(f, "Invalid separator: {}", c),
+            ParserError::InvalidRankLabel(row) => write!(f, "Invalid rank label in row {}", row),
+            ParserError::InvalidRecord { line, record } => {
+                write!(f, "Invalid record at line {}: {}", line, record)
+            }
+            ParserError::NonAscii(byte, row, col) => write!(
+                f,
+                "Non ASCII byte 0x{:02x} at row {}, column {}",
+                byte, row, col
+            ),
+            ParserError::TrailingContent(row) => {
+                write!(f, "Trailing content after the board in row {}", row)
+            }
+            ParserError::PieceLimitExceeded { color, kind, limit } => write!(
+                f,
+                "Too many {:?} {:?} pieces, at most {} allowed",
+                color, kind, limit
+            ),
+            ParserError::InvalidGzip => write!(f, "Invalid gzip file"),
         }
     }
 }
 
-#[doc = "Add a piece to the board at the given position."]
-fn add_to_board(board: &mut Board, position: (usize, usize), c: char) -> Result<(), ParserError> {
-    if let Some(piece) = Piece::from_char(c, position)? {
+#[doc = "Parses a board from its text representation, see from_string."]
+impl TryFrom<&str> for Board {
+    type Error = ParserError;
+
+    fn try_from(input: &str) -> Result<Board, ParserError> {
+        from_string(input)
+    }
+}
+
+#[doc = "Parses a board from its text representation, see from_string."]
+impl FromStr for Board {
+    type Err = ParserError;
+
+    fn from_str(input: &str) -> Result<Board, ParserError> {
+        from_string(input)
+    }
+}
+
+#[doc = "Add a piece written in the given notation to the board at the given position,
+refusing it when the board already holds as many pieces of its color and kind as the limits allow."]
+fn add_to_board(
+    board: &mut Board,
+    position: (usize, usize),
+    c: char,
+    notation: Notation,
+    limits: Option<&PieceLimits>,
+) -> Result<(), ParserError> {
+    if let Some(piece) = Piece::from_char_with(c, position, notation)? {
+        if let Some(limits) = limits {
+            limits.guard(board, &piece)?;
+        }
         board.place_piece(piece)?;
     }
     Ok(())
@@ -79,10 +206,15 @@ fn file_is_empty(file: &File) -> bool {
     file.metadata().map(|m| m.len()).unwrap_or(0) == 0
 }
 
+#[doc = "Check if a file is larger than the given size in bytes."]
+fn file_is_too_large(file: &File, max_size: u64) -> bool {
+    file.metadata().map(|m| m.len()).unwrap_or(0) > max_size
+}
+
 #[doc = "Check if the board size is valid."]
-fn guard_board_size(tokens: usize) -> Result<(), ParserError> {
-    if tokens != 8 {
-        return if tokens > 8 {
+fn guard_board_size(tokens: usize, size: usize) -> Result<(), ParserError> {
+    if tokens != size {
+        return if tokens > size {
             Err(ParserError::InvalidBoardSize)
         } else {
             Err(ParserError::NotEnoughTokens)
@@ -91,27 +223,536 @@ fn guard_board_size(tokens: usize) -> Result<(), ParserError> {
     Ok(())
 }
 
+#[doc = "Characters that separate squares in rows written for other tools, such as \"T,D,A\"."]
+const SEPARATORS: [char; 3] = [',', ';', '|'];
+
+#[doc = "Check that the squares of a row are only separated by whitespace, so that a row such as
+\"T,D,A\" is not mistaken for a single long token. Besides the usual separators, a punctuation character
+between two squares of a token, such as the - in \"T-D\", is taken as a separator. Any other stray character
+is left to be reported as an invalid piece."]
+fn guard_separators(line: &str) -> Result<(), ParserError> {
+    if let Some(c) = line.chars().find(|c| SEPARATORS.contains(c)) {
+        return Err(ParserError::InvalidSeparator(c));
+    }
+    let is_square = |c: char| c.is_ascii_alphanumeric() || EMPTY_SQUARE_CHARS.contains(&c);
+    for token in line.split_whitespace() {
+        let chars = token.chars().collect::<Vec<char>>();
+        let separator = chars.windows(3).find(|window| {
+            is_square(window[0])
+                && window[1].is_ascii_punctuation()
+                && !is_square(window[1])
+                && is_square(window[2])
+        });
+        if let Some(window) = separator {
+            return Err(ParserError::InvalidSeparator(window[1]));
+        }
+    }
+    Ok(())
+}
+
+#[doc = "Strip the optional rank label of a row, such as \"8:\" in \"8: T D A C R A C T\".
+Labels must count down from the board size to 1, and either every row is labeled or none is."]
+fn strip_rank_label<'a>(
+    line: &'a str,
+    labeled: &mut Option<bool>,
+    row: usize,
+    size: usize,
+) -> Result<&'a str, ParserError> {
+    let label = line.split_once(':').and_then(|(label, rest)| {
+        let label = label.trim();
+        let is_number = !label.is_empty() && label.chars().all(|c| c.is_ascii_digit());
+        is_number.then(|| (label.parse::<usize>().ok(), rest))
+    });
+    if *labeled.get_or_insert(label.is_some()) != label.is_some() {
+        return Err(ParserError::InvalidRankLabel(row));
+    }
+    match label {
+        Some((rank, rest)) if rank.is_some() && rank == size.checked_sub(row) => Ok(rest),
+        Some(_) => Err(ParserError::InvalidRankLabel(row)),
+        None => Ok(line),
+    }
+}
+
+#[doc = "Join the squares of a row into one character per square. A numeric token such as the 3 in
+\"T 3 D 3\" stands for that many empty squares, as in FEN. Runs longer than the board are cut
+just past its size, which is enough for the row to be reported as too wide."]
+fn expand_row(line: &str, size: usize) -> String {
+    line.split_whitespace()
+        .map(|token| match token.parse::<usize>() {
+            Ok(empty) => EMPTY_SQUARE_CHARS[0]
+                .to_string()
+                .repeat(empty.min(size + 1)),
+            Err(_) => token.to_string(),
+        })
+        .collect()
+}
+
+#[doc = "Check that nothing but blank lines follows the last row of a complete board."]
+fn guard_trailing_content(
+    line: &str,
+    rows: usize,
+    size: usize,
+    row: usize,
+) -> Result<(), ParserError> {
+    if rows == size && !line.trim().is_empty() {
+        return Err(ParserError::TrailingContent(row));
+    }
+    Ok(())
+}
+
+#[doc = "Check that a row has the same width as the rows before it."]
+fn guard_row_width(width: &mut Option<usize>, found: usize, row: usize) -> Result<(), ParserError> {
+    match *width {
+        Some(expected) if expected != found => Err(ParserError::RaggedBoard {
+            expected,
+            found,
+            row,
+        }),
+        Some(_) => Ok(()),
+        None => {
+            *width = Some(found);
+            Ok(())
+        }
+    }
+}
+
 #[doc = "Parse a board from a file given its path"]
 pub fn from_path(path: &str) -> Result<Board, ParserError> {
+    from_path_with_limit(path, DEFAULT_MAX_FILE_SIZE)
+}
+
+#[doc = "Parse a board from a file given its path, reading its pieces in the given notation instead of
+guessing it. from_path is the same as passing Notation::Spanish."]
+pub fn from_path_with(path: &str, notation: Notation) -> Result<Board, ParserError> {
+    from_bytes_with(
+        &read_board_file(path, DEFAULT_MAX_FILE_SIZE)?,
+        notation,
+        None,
+    )
+}
+
+#[doc = "Parse a board from a file given its path, refusing boards with more pieces than the given limits allow."]
+pub fn from_path_with_limits(path: &str, limits: &PieceLimits) -> Result<Board, ParserError> {
+    from_bytes_with(
+        &read_board_file(path, DEFAULT_MAX_FILE_SIZE)?,
+        Notation::Spanish,
+        Some(limits),
+    )
+}
+
+#[doc = "Check that a number of pieces fits on the board, one per square."]
+fn guard_capacity(board: &Board, pieces: usize) -> Result<(), ParserError> {
+    if pieces > board.size() * board.size() {
+        return Err(ParserError::TooManyPieces);
+    }
+    Ok(())
+}
+
+#[doc = "Parse a board from a file given its path, refusing files larger than max_size bytes.
+No more than max_size bytes are ever read from the file."]
+pub fn from_path_with_limit(path: &str, max_size: u64) -> Result<Board, ParserError> {
+    from_bytes(&read_board_file(path, max_size)?)
+}
+
+#[doc = "Read the bytes of a board file, decompressing it if needed, refusing files larger than max_size bytes.
+A compressed file is refused as well when it decompresses to more than max_size bytes."]
+fn read_board_file(path: &str, max_size: u64) -> Result<Vec<u8>, ParserError> {
     let file = File::open(path).map_err(|_| ParserError::FileNotExists)?;
     if file_is_empty(&file) {
         return Err(ParserError::FileIsEmpty);
     }
-    let reader = BufReader::new(file);
-    let mut board = Board::default_board();
+    if file_is_too_large(&file, max_size) {
+        return Err(ParserError::FileTooLarge);
+    }
+    #[cfg(feature = "gzip")]
+    let mut file = file;
+    let mut bytes = Vec::new();
+    #[cfg(feature = "gzip")]
+    if is_gzip(path, &mut file) {
+        flate2::read::GzDecoder::new(file)
+            .take(max_size + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|_| ParserError::InvalidGzip)?;
+        if bytes.len() as u64 > max_size {
+            return Err(ParserError::FileTooLarge);
+        }
+        return Ok(bytes);
+    }
+    file.take(max_size)
+        .read_to_end(&mut bytes)
+        .map_err(|_| ParserError::FileNotExists)?;
+    Ok(bytes)
+}
+
+#[doc = "Parse a board from the raw bytes of a board file. Board files are plain ASCII, so every byte
+is checked and mapped to its character directly, without decoding UTF-8. A byte outside of ASCII
+returns a NonAscii error with its row and column in the file."]
+pub fn from_bytes(bytes: &[u8]) -> Result<Board, ParserError> {
+    from_bytes_with(bytes, Notation::Spanish, None)
+}
+
+#[doc = "Parse a board from the raw bytes of a board file, reading its pieces in the given notation
+and within the given limits, if any."]
+fn from_bytes_with(
+    bytes: &[u8],
+    notation: Notation,
+    limits: Option<&PieceLimits>,
+) -> Result<Board, ParserError> {
+    parse_lines(decode_ascii(bytes)?.lines(), notation, None, limits)
+}
+
+#[doc = "Map the bytes of a board file to its text, one character per byte, once every byte is checked to be ASCII."]
+fn decode_ascii(bytes: &[u8]) -> Result<String, ParserError> {
+    guard_ascii(bytes)?;
+    Ok(bytes.iter().map(|&byte| byte as char).collect())
+}
+
+#[doc = "Check that every byte is ASCII."]
+fn guard_ascii(bytes: &[u8]) -> Result<(), ParserError> {
+    for (row, line) in bytes.split(|&byte| byte == b'\n').enumerate() {
+        if let Some(col) = line.iter().position(|byte| !byte.is_ascii()) {
+            return Err(ParserError::NonAscii(line[col], row, col));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "gzip")]
+#[doc = "Check if a file is gzip compressed, either by its .gz extension or by its leading magic bytes.
+The file is rewound to its start afterwards."]
+fn is_gzip(path: &str, file: &mut File) -> bool {
+    if path.ends_with(".gz") {
+        return true;
+    }
+    let mut magic = [0u8; 2];
+    let is_gzip = file.read_exact(&mut magic).is_ok() && magic == GZIP_MAGIC;
+    file.seek(SeekFrom::Start(0)).is_ok() && is_gzip
+}
+
+#[doc = "Parse a board from a file given its path, requiring exactly one white and one black piece
+as the two piece exercise expects."]
+pub fn from_path_strict(path: &str) -> Result<Board, ParserError> {
+    let board = from_path(path)?;
+    guard_two_pieces(&board)?;
+    Ok(board)
+}
+
+#[doc = "Check that a board holds exactly one white and one black piece."]
+fn guard_two_pieces(board: &Board) -> Result<(), ParserError> {
+    let whites = board.pieces().filter(|piece| piece.is_white()).count();
+    let blacks = board.pieces().filter(|piece| piece.is_black()).count();
+    match (whites, blacks) {
+        (1, 1) => Ok(()),
+        (w, b) if w > 1 || b > 1 => Err(ParserError::TooManyPieces),
+        _ => Err(ParserError::NotEnoughPieces),
+    }
+}
+
+#[doc = "Parse a board from its text representation."]
+pub fn from_string(input: &str) -> Result<Board, ParserError> {
+    parse_lines(input.lines(), Notation::Spanish, None, None)
+}
+
+#[doc = "Caps on how many pieces of a given color and kind a board may hold, for variants such as one queen per side.
+A color and kind without a limit may have any number of pieces, so the default limits accept every board."]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PieceLimits {
+    limits: BTreeMap<(Color, PieceKind), usize>,
+}
+
+impl PieceLimits {
+    #[doc = "Returns limits that accept any number of pieces."]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[doc = "Returns these limits allowing at most limit pieces of the given color and kind."]
+    pub fn with_limit(mut self, color: Color, kind: PieceKind, limit: usize) -> Self {
+        self.limits.insert((color, kind), limit);
+        self
+    }
+
+    #[doc = "Returns the most pieces of the given color and kind allowed, or None if there is no limit."]
+    pub fn limit(&self, color: Color, kind: PieceKind) -> Option<usize> {
+        self.limits.get(&(color, kind)).copied()
+    }
+
+    #[doc = "Checks that a piece can be placed on a board without going over the limit of its color and kind,
+    counting the pieces of that color and kind already on the board. Otherwise a PieceLimitExceeded error is returned."]
+    fn guard(&self, board: &Board, piece: &Piece) -> Result<(), ParserError> {
+        let key = (piece.color(), piece.kind);
+        let Some(&limit) = self.limits.get(&key) else {
+            return Ok(());
+        };
+        let count = board
+            .pieces()
+            .filter(|other| (other.color(), other.kind) == key)
+            .count();
+        if count >= limit {
+            return Err(ParserError::PieceLimitExceeded {
+                color: key.0,
+                kind: key.1,
+                limit,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[doc = "Parse a board from its text representation, refusing boards with more pieces than the given limits allow."]
+pub fn from_string_with_limits(input: &str, limits: &PieceLimits) -> Result<Board, ParserError> {
+    parse_lines(input.lines(), Notation::Spanish, None, Some(limits))
+}
+
+#[doc = "Parse a board from its text representation, leaving an empty square wherever an invalid piece is found
+instead of failing. It returns the board along with the (row, col, char) of every invalid piece, in the order
+they were found. Any other error is returned just like from_string does."]
+pub fn from_string_lossy(input: &str) -> Result<(Board, Vec<ParseProblem>), ParserError> {
+    let mut problems = Vec::new();
+    let board = parse_lines(input.lines(), Notation::Spanish, Some(&mut problems), None)?;
+    Ok((board, problems))
+}
+
+#[doc = "Parse a board from the color,kind,row,col records written by Board::to_records.
+The records may be preceded by a size header, as written for boards that aren't 8x8, otherwise the board is 8x8.
+The color,kind,row,col header line is optional and blank lines are skipped. A line that isn't a valid record
+returns an InvalidRecord error with its line number, counted from 1, and a piece off the board an InvalidPosition error.
+More records than squares return a TooManyPieces error before any piece is placed."]
+pub fn from_records(input: &str) -> Result<Board, ParserError> {
+    let mut lines = input
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .peekable();
+    let size = match lines.peek().and_then(|&(_, line)| parse_size_header(line)) {
+        Some(size) => {
+            lines.next();
+            size?
+        }
+        None => 8,
+    };
+    let mut board = Board::with_size(size)?;
+    lines.next_if(|&(_, line)| line == "color,kind,row,col");
+    let records = lines
+        .filter(|&(_, line)| !line.is_empty())
+        .collect::<Vec<(usize, &str)>>();
+    guard_capacity(&board, records.len())?;
+    for (line, record) in records {
+        let piece = parse_record(record).ok_or_else(|| ParserError::InvalidRecord {
+            line,
+            record: record.to_string(),
+        })?;
+        board.place_piece(piece)?;
+    }
+    Ok(board)
+}
+
+#[doc = "Parse a single color,kind,row,col record into a piece."]
+fn parse_record(record: &str) -> Option<Piece> {
+    let fields = record.split(',').map(str::trim).collect::<Vec<&str>>();
+    let [color, kind, row, col] = fields.as_slice() else {
+        return None;
+    };
+    let color = match *color {
+        "white" => Color::White,
+        "black" => Color::Black,
+        _ => return None,
+    };
+    let kind = match *kind {
+        "rook" => PieceKind::Rook,
+        "knight" => PieceKind::Knight,
+        "king" => PieceKind::King,
+        "bishop" => PieceKind::Bishop,
+        "queen" => PieceKind::Queen,
+        "pawn" => PieceKind::Pawn,
+        _ => return None,
+    };
+    let position = (row.parse().ok()?, col.parse().ok()?);
+    Some(Piece {
+        color,
+        kind,
+        position,
+    })
+}
+
+#[doc = "Parse every board of a file given its path. Boards are separated by one or more blank lines
+and each of them is parsed independently. The file is read like from_path does, so files larger than
+DEFAULT_MAX_FILE_SIZE or holding a byte outside of ASCII are refused before any board is parsed.
+The first error found in a board is returned along with the index of its board."]
+pub fn from_path_multi(path: &str) -> Result<Vec<Board>, ParserError> {
+    let input = decode_ascii(&read_board_file(path, DEFAULT_MAX_FILE_SIZE)?)?;
+    let mut blocks: Vec<Vec<&str>> = Vec::new();
+    let mut block = Vec::new();
+    for line in input.lines() {
+        if line.trim().is_empty() {
+            if !block.is_empty() {
+                blocks.push(std::mem::take(&mut block));
+            }
+        } else {
+            block.push(line);
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    if blocks.is_empty() {
+        return Err(ParserError::FileIsEmpty);
+    }
+    blocks
+        .into_iter()
+        .enumerate()
+        .map(|(index, block)| {
+            parse_lines(block, Notation::Spanish, None, None).map_err(|err| ParserError::InBlock {
+                index,
+                error: Box::new(err),
+            })
+        })
+        .collect()
+}
+
+#[doc = "Check a board in its text representation and return every error found, instead of stopping at the first one.
+Invalid pieces and rows of the wrong width are reported with their location, and an empty list means the input parses."]
+pub fn validate_all(input: &str) -> Vec<ParserError> {
+    let mut errors = Vec::new();
+    let mut lines = input.lines().peekable();
+    let size = match lines.peek().and_then(|line| parse_size_header(line)) {
+        Some(size) => {
+            lines.next();
+            match size {
+                Ok(size) => size,
+                Err(err) => {
+                    errors.push(err);
+                    8
+                }
+            }
+        }
+        None => 8,
+    };
 
     let mut rows = 0;
-    for (i, line) in reader.lines().enumerate() {
-        let line = line.unwrap();
-        let piece_line = line.split_whitespace().collect::<String>();
+    let mut width = None;
+    let mut labeled = None;
+    for (i, line) in lines.enumerate() {
+        if let Err(err) = guard_trailing_content(line, rows, size, i) {
+            errors.push(err);
+            continue;
+        }
+        if rows == size {
+            continue;
+        }
+        let line = match strip_rank_label(line, &mut labeled, i, size) {
+            Ok(line) => line,
+            Err(err) => {
+                errors.push(err);
+                rows += 1;
+                continue;
+            }
+        };
+        if let Err(err) = guard_separators(line) {
+            errors.push(err);
+            rows += 1;
+            continue;
+        }
+        let piece_line = expand_row(line, size);
+        let found = piece_line.chars().count();
 
-        guard_board_size(piece_line.len())?;
+        if let Err(err) = guard_row_width(&mut width, found, i) {
+            errors.push(err);
+        } else if let Err(err) = guard_board_size(found, size) {
+            errors.push(ParserError::At {
+                row: i,
+                col: found.min(size),
+                error: Box::new(err),
+            });
+        }
+        for (j, c) in piece_line.chars().take(size).enumerate() {
+            if let Err(err) = Piece::from_char(c, (i, j)) {
+                errors.push(ParserError::At {
+                    row: i,
+                    col: j,
+                    error: Box::new(err.into()),
+                });
+            }
+        }
+        rows += 1;
+    }
+    if rows != size {
+        errors.push(ParserError::InvalidBoardSize);
+    }
+    errors
+}
+
+#[doc = "Parse an optional header line such as \"size: 10\" declaring the board dimension.
+Returns None when the line is not a header."]
+fn parse_size_header(line: &str) -> Option<Result<usize, ParserError>> {
+    let size = line.trim().strip_prefix("size:")?;
+    Some(
+        size.trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|size| (1..=Board::MAX_SIZE).contains(size))
+            .ok_or(ParserError::InvalidBoardSize),
+    )
+}
+
+#[doc = "Parse a board from its rows, one per line. The rows may be preceded by a size header,
+otherwise the board is 8x8, and each row may start with its rank label. Pieces are read in the given notation.
+When problems is given, invalid pieces are recorded there as (row, col, char) and left as empty squares
+instead of returning an error. When limits are given, each piece is checked against them as it is placed."]
+fn parse_lines<I>(
+    lines: I,
+    notation: Notation,
+    mut problems: Option<&mut Vec<ParseProblem>>,
+    limits: Option<&PieceLimits>,
+) -> Result<Board, ParserError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut lines = lines.into_iter().peekable();
+    let size = match lines
+        .peek()
+        .and_then(|line| parse_size_header(line.as_ref()))
+    {
+        Some(size) => {
+            lines.next();
+            size?
+        }
+        None => 8,
+    };
+    let mut board = Board::with_size(size)?;
+
+    let mut rows = 0;
+    let mut width = None;
+    let mut labeled = None;
+    for (i, line) in lines.enumerate() {
+        guard_trailing_content(line.as_ref(), rows, size, i)?;
+        if rows == size {
+            continue;
+        }
+        let line = strip_rank_label(line.as_ref(), &mut labeled, i, size)?;
+        guard_separators(line)?;
+        let piece_line = expand_row(line, size);
+
+        let found = piece_line.chars().count();
+
+        guard_row_width(&mut width, found, i)?;
+        guard_board_size(found, size)?;
         for (j, c) in piece_line.chars().enumerate() {
-            add_to_board(&mut board, (i, j), c)?;
+            let placed = add_to_board(&mut board, (i, j), c, notation, limits);
+            match (placed, problems.as_mut()) {
+                (Err(ParserError::InvalidPiece(c)), Some(problems)) => problems.push((i, j, c)),
+                (placed, _) => placed.map_err(|err| ParserError::At {
+                    row: i,
+                    col: j,
+                    error: Box::new(err),
+                })?,
+            }
         }
         rows += 1;
     }
-    if rows != 8 {
+    if rows != size {
         return Err(ParserError::InvalidBoardSize);
     }
     Ok(board)
@@ -122,6 +763,52 @@ mod tests {
     use super::*;
     use crate::board::Board;
     use crate::piece::{Color, PieceKind};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[doc = "A temporary file that is removed when dropped, even if the test using it panics."]
+    struct TempFile(PathBuf);
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[doc = "Writes the contents to a unique temporary file, runs f with its path and removes the file."]
+    fn with_temp_board<R>(contents: impl AsRef<[u8]>, f: impl FnOnce(&str) -> R) -> R {
+        with_temp_board_named("txt", contents, f)
+    }
+
+    #[doc = "Same as with_temp_board, but the temporary file name ends with the given extension."]
+    fn with_temp_board_named<R>(
+        extension: &str,
+        contents: impl AsRef<[u8]>,
+        f: impl FnOnce(&str) -> R,
+    ) -> R {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let file = TempFile(std::env::temp_dir().join(format!(
+            "rustmate_{}_{}.{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            extension
+        )));
+        std::fs::write(&file.0, contents).unwrap();
+        f(file.0.to_str().unwrap())
+    }
+
+    #[test]
+    fn test_temp_board_is_removed_after_a_panic() {
+        let mut kept = String::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            with_temp_board("", |path| {
+                kept = path.to_string();
+                panic!("failing test");
+            })
+        }));
+        assert!(result.is_err());
+        assert!(!std::path::Path::new(&kept).exists());
+    }
 
     #[test]
     fn test_file_not_exist() -> Result<(), Box<dyn Error>> {
@@ -133,14 +820,59 @@ mod tests {
     #[test]
     fn test_file_is_empty() -> Result<(), Box<dyn Error>> {
         let input = "";
-        let filename = "tests/fixtures/ejemplo05_vacio.txt";
-        let mut file = File::create(filename).unwrap();
-        write!(file, "{}", input).unwrap();
+        let result = with_temp_board(input, from_path);
+        assert_eq!(result, Err(ParserError::FileIsEmpty));
+        Ok(())
+    }
 
-        let result = from_path(filename);
+    #[test]
+    fn test_file_too_large() -> Result<(), Box<dyn Error>> {
+        let input = "_ _ _ _ _ _ _ _\n".repeat(300);
+        let result = with_temp_board(input, from_path);
+        let limited_result = from_path_with_limit("tests/fixtures/ejemplo01.txt", 64);
 
-        std::fs::remove_file(filename).unwrap();
-        assert_eq!(result, Err(ParserError::FileIsEmpty));
+        assert_eq!(result, Err(ParserError::FileTooLarge));
+        assert_eq!(result.unwrap_err().to_string(), "File too large");
+        assert_eq!(limited_result, Err(ParserError::FileTooLarge));
+        Ok(())
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_file() -> Result<(), Box<dyn Error>> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&std::fs::read("tests/fixtures/ejemplo01.txt")?)?;
+        let compressed = encoder.finish()?;
+
+        let expected_board = from_path("tests/fixtures/ejemplo01.txt")?;
+        assert_eq!(
+            with_temp_board_named("txt.gz", &compressed, from_path)?,
+            expected_board
+        );
+        assert_eq!(with_temp_board(&compressed, from_path)?, expected_board);
+        Ok(())
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_file_too_large_or_corrupt() -> Result<(), Box<dyn Error>> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all("_ _ _ _ _ _ _ _\n".repeat(300).as_bytes())?;
+        let compressed = encoder.finish()?;
+        assert!(compressed.len() as u64 <= DEFAULT_MAX_FILE_SIZE);
+        let result = with_temp_board(compressed, from_path);
+        assert_eq!(result, Err(ParserError::FileTooLarge));
+
+        let corrupt = [&GZIP_MAGIC[..], b"not a gzip stream"].concat();
+        let result = with_temp_board(corrupt, from_path);
+        assert_eq!(result, Err(ParserError::InvalidGzip));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid gzip file");
         Ok(())
     }
 
@@ -148,13 +880,7 @@ mod tests {
     fn test_invalid_board_size() -> Result<(), Box<dyn Error>> {
         let input = "_ _ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\
         \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n";
-        let filename = "tests/fixtures/ejemplo05_board_invalido.txt";
-        let mut file = File::create(filename).unwrap();
-        write!(file, "{}", input).unwrap();
-
-        let result = from_path(filename);
-
-        std::fs::remove_file(filename).unwrap();
+        let result = with_temp_board(input, from_path);
         assert_eq!(result, Err(ParserError::InvalidBoardSize));
         assert_eq!(result.unwrap_err().to_string(), "Invalid board size");
         Ok(())
@@ -164,33 +890,193 @@ mod tests {
     fn test_not_enough_tokens() -> Result<(), Box<dyn Error>> {
         let input = "_ _ _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _\n\
         _ _ _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _\n_ _ _ _\n";
-        let filename = "tests/fixtures/ejemplo06_not_enough_tokens.txt";
-        let mut file = File::create(filename).unwrap();
-        write!(file, "{}", input).unwrap();
-
-        let result = from_path(filename);
-
-        std::fs::remove_file(filename).unwrap();
+        let result = with_temp_board(input, from_path);
         assert_eq!(result, Err(ParserError::NotEnoughTokens));
         assert_eq!(result.unwrap_err().to_string(), "Not enough tokens");
         Ok(())
     }
 
+    #[test]
+    fn test_ragged_board() -> Result<(), Box<dyn Error>> {
+        let input = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\
+        \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _ _\n";
+        let result = with_temp_board(input, from_path);
+        assert_eq!(
+            result,
+            Err(ParserError::RaggedBoard {
+                expected: 8,
+                found: 9,
+                row: 7,
+            })
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Ragged board: row 7 has 9 columns, expected 8"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_ascii_square_is_an_invalid_piece() {
+        let input = "_ _ _ _ _ _ _ _\n"
+            .repeat(8)
+            .replacen("_ _ _ _ _ _ _ _", "ñ _ _ _ _ _ _ _", 1);
+        let invalid = ParserError::At {
+            row: 0,
+            col: 0,
+            error: Box::new(ParserError::InvalidPiece('ñ')),
+        };
+        assert_eq!(validate_all(&input), vec![invalid]);
+        assert_eq!(
+            from_string(&input),
+            Err(ParserError::At {
+                row: 0,
+                col: 0,
+                error: Box::new(ParserError::InvalidPiece('ñ')),
+            })
+        );
+
+        let input = "_ _ _ _ _ _ _ _\n"
+            .repeat(8)
+            .replacen("_ _ _ _ _ _ _ _", "ñ _ _ _ _ _ t", 1);
+        assert_eq!(from_string(&input), Err(ParserError::NotEnoughTokens));
+    }
+
+    #[test]
+    fn test_comma_separated_row() -> Result<(), Box<dyn Error>> {
+        let input = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_,_,_,D,_,_,_,_\n_ _ _ _ _ _ _ _\
+        \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ t _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n";
+        let result = with_temp_board(input, from_path);
+        assert_eq!(result, Err(ParserError::InvalidSeparator(',')));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid separator: ,");
+        Ok(())
+    }
+
+    #[test]
+    fn test_stray_punctuation_is_an_invalid_piece() -> Result<(), Box<dyn Error>> {
+        let input = "_ _ _ _ _ _ _ _\n"
+            .repeat(8)
+            .replacen("_ _ _ _ _ _ _ _", "_ _ ? _ _ _ _ _", 1);
+        let invalid = ParserError::At {
+            row: 0,
+            col: 2,
+            error: Box::new(ParserError::InvalidPiece('?')),
+        };
+        assert_eq!(validate_all(&input), vec![invalid]);
+        assert_eq!(
+            from_string(&input),
+            Err(ParserError::At {
+                row: 0,
+                col: 2,
+                error: Box::new(ParserError::InvalidPiece('?')),
+            })
+        );
+        let (board, problems) = from_string_lossy(&input)?;
+        assert!(board.is_empty());
+        assert_eq!(problems, vec![(0, 2, '?')]);
+
+        let input = "_ _ _ _ _ _ _ _\n"
+            .repeat(8)
+            .replacen("_ _ _ _ _ _ _ _", "_ _ T-D _ _ _ _", 1);
+        assert_eq!(from_string(&input), Err(ParserError::InvalidSeparator('-')));
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_ascii_byte() -> Result<(), Box<dyn Error>> {
+        let mut input = "_ _ _ _ _ _ _ _\n".repeat(8).into_bytes();
+        input[16 * 3 + 4] = 0xe9;
+        let result = with_temp_board(input, from_path);
+        assert_eq!(result, Err(ParserError::NonAscii(0xe9, 3, 4)));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Non ASCII byte 0xe9 at row 3, column 4"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_non_ascii_byte() {
+        let mut input = format!("{0}\n{0}", "_ _ _ _ _ _ _ _\n".repeat(8)).into_bytes();
+        input[129 + 16 + 2] = 0xff;
+        input[129 + 16 + 3] = 0xfe;
+        let result = with_temp_board(input, from_path_multi);
+        assert_eq!(result, Err(ParserError::NonAscii(0xff, 10, 2)));
+    }
+
+    #[test]
+    fn test_from_bytes() -> Result<(), Box<dyn Error>> {
+        let board = from_bytes("_ _ _ _ _ _ _ _\n".repeat(8).as_bytes())?;
+        assert!(board.is_empty());
+        assert_eq!(
+            from_bytes("size: 1\n\u{00e9}\n".as_bytes()),
+            Err(ParserError::NonAscii(0xc3, 1, 0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_trailing_content() -> Result<(), Box<dyn Error>> {
+        let input = format!("{}this is not a row\n", "_ _ _ _ _ _ _ _\n".repeat(8));
+        let result = with_temp_board(input, from_path);
+        assert_eq!(result, Err(ParserError::TrailingContent(8)));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Trailing content after the board in row 8"
+        );
+
+        let input = format!("{}\n\n", "_ _ _ _ _ _ _ _\n".repeat(8));
+        assert!(with_temp_board(input, from_path)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_file_too_large() {
+        let input = format!("{}\n", "_ _ _ _ _ _ _ _\n".repeat(8)).repeat(40);
+        let result = with_temp_board(input, from_path_multi);
+        assert_eq!(result, Err(ParserError::FileTooLarge));
+    }
+
+    #[test]
+    fn test_strict_rejects_three_pieces() -> Result<(), Box<dyn Error>> {
+        let input = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ D _ _ _ _\n_ _ _ _ _ _ _ _\
+        \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ t _\n_ _ _ _ _ _ _ _\n_ p _ _ _ _ _ _\n";
+        let (lenient, result) =
+            with_temp_board(input, |path| (from_path(path), from_path_strict(path)));
+
+        assert!(lenient.is_ok());
+        assert_eq!(result, Err(ParserError::TooManyPieces));
+        assert_eq!(result.unwrap_err().to_string(), "Too many pieces");
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_accepts_two_pieces() -> Result<(), Box<dyn Error>> {
+        let board = from_path_strict("tests/fixtures/ejemplo01.txt")?;
+        assert_eq!(board, from_path("tests/fixtures/ejemplo01.txt")?);
+        assert_eq!(
+            from_path_strict("tests/fixtures/ejemplo05.txt"),
+            Err(ParserError::NotEnoughPieces)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_token() -> Result<(), Box<dyn Error>> {
         let input = "_ _ _ W _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\
         \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n";
-        let filename = "tests/fixtures/ejemplo07_invalid_token.txt";
-        let mut file = File::create(filename).unwrap();
-        write!(file, "{}", input).unwrap();
-
-        let result = from_path(filename);
-
-        std::fs::remove_file(filename).unwrap();
-        assert_eq!(result, Err(ParserError::InvalidPiece('W')));
+        let result = with_temp_board(input, from_path);
+        assert_eq!(
+            result,
+            Err(ParserError::At {
+                row: 0,
+                col: 3,
+                error: Box::new(ParserError::InvalidPiece('W')),
+            })
+        );
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Invalid piece kind character: W"
+            "Invalid piece kind character: W at row 0, column 3"
         );
         Ok(())
     }
@@ -199,10 +1085,6 @@ mod tests {
     fn test_valid_token() -> Result<(), Box<dyn Error>> {
         let input = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\
         \n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n_ _ _ _ _ _ d _\n";
-        let filename = "tests/fixtures/ejemplo08_valid_token.txt";
-        let mut file = File::create(filename).unwrap();
-        write!(file, "{}", input).unwrap();
-
         let mut expected_board = Board::default_board();
         let piece = Piece {
             kind: PieceKind::Queen,
@@ -211,9 +1093,7 @@ mod tests {
         };
         expected_board.place_piece(piece)?;
 
-        let current_board = from_path(filename)?;
-
-        std::fs::remove_file(filename).unwrap();
+        let current_board = with_temp_board(input, from_path)?;
         assert_eq!(current_board, expected_board);
         Ok(())
     }
@@ -1,5 +1,10 @@
+use crate::board::BoardError;
+use std::collections::VecDeque;
 use std::error::Error;
 
+#[doc = "Characters that mark an empty square in a board file."]
+pub const EMPTY_SQUARE_CHARS: [char; 2] = ['_', '.'];
+
 #[doc = "Enum is used to represent an error that can occur when trying to create a Piece struct from a character.
 If the character does not represent a valid chess piece, an InvalidPieceKind error is returned."]
 #[derive(Debug, PartialEq)]
@@ -18,14 +23,24 @@ impl std::fmt::Display for PieceError {
 impl Error for PieceError {}
 
 #[doc = "Represents the color of a chess piece."]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Color {
     White,
     Black,
 }
 
+impl Color {
+    #[doc = "Returns the other color."]
+    pub fn opposite(&self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
 #[doc = "Represents the kind of a chess piece."]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PieceKind {
     Rook,
     Knight,
@@ -35,19 +50,159 @@ pub enum PieceKind {
     Pawn,
 }
 
-#[doc = "Represents a chess piece with a color and a kind."]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[doc = "Represents the letters a board file writes its pieces with.
+Spanish uses R, D, A, C, T and P, English uses K, Q, R, B, N and P, and both write white pieces in lowercase.
+Fen uses the English letters with the FEN case convention instead, white pieces in uppercase."]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    #[default]
+    Spanish,
+    English,
+    Fen,
+}
+
+#[doc = "Represents the direction pawns advance in, relative to how rows and columns are indexed.
+Horizontal pawns advance along their row, white ones towards higher columns and black ones towards lower columns.
+Vertical pawns advance along their column the way the board file is read, white ones towards row 0 and black ones towards the last row.
+Square names, FEN and rendering always read rows as ranks, as the board file is laid out, so they only match the
+direction pawns advance in on Vertical boards. Castling and UCI moves need ranks to be rows: Horizontal boards can't
+castle and Board::apply_move_uci returns an UnsupportedOrientation error on them."]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    #[doc = "Returns the (row, column) step a pawn of the given color advances by."]
+    pub fn pawn_direction(&self, color: Color) -> (i32, i32) {
+        match (self, color) {
+            (Orientation::Horizontal, Color::White) => (0, 1),
+            (Orientation::Horizontal, Color::Black) => (0, -1),
+            (Orientation::Vertical, Color::White) => (-1, 0),
+            (Orientation::Vertical, Color::Black) => (1, 0),
+        }
+    }
+}
+
+impl PieceKind {
+    #[doc = "Returns the conventional material value of this kind of piece, in pawns.
+    The king is priceless and is worth nothing in material counts."]
+    pub fn value(&self) -> u32 {
+        match self {
+            PieceKind::Pawn => 1,
+            PieceKind::Knight | PieceKind::Bishop => 3,
+            PieceKind::Rook => 5,
+            PieceKind::Queen => 9,
+            PieceKind::King => 0,
+        }
+    }
+
+    #[doc = "Returns the minimum number of moves this kind of piece needs to go from one position to another
+    on an empty board_size x board_size board, or None if the target can't be reached.
+    Pawns are measured as white pawns, which only advance towards higher columns."]
+    pub fn move_distance(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        board_size: usize,
+    ) -> Option<usize> {
+        let in_bounds = |(x, y): (usize, usize)| x < board_size && y < board_size;
+        if !in_bounds(from) || !in_bounds(to) {
+            return None;
+        }
+        let dx = from.0.abs_diff(to.0);
+        let dy = from.1.abs_diff(to.1);
+        match self {
+            PieceKind::King => Some(dx.max(dy)),
+            PieceKind::Rook => Some(usize::from(dx != 0) + usize::from(dy != 0)),
+            _ => self.breadth_first_distance(from, to, board_size),
+        }
+    }
+
+    fn breadth_first_distance(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+        board_size: usize,
+    ) -> Option<usize> {
+        let mut distances = vec![vec![None; board_size]; board_size];
+        distances[from.0][from.1] = Some(0);
+        let mut queue = VecDeque::from([from]);
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[position.0][position.1]?;
+            if position == to {
+                return Some(distance);
+            }
+            let piece = Piece {
+                color: Color::White,
+                kind: *self,
+                position,
+            };
+            for (x, y) in piece.reachable_squares(board_size) {
+                if distances[x][y].is_none() {
+                    distances[x][y] = Some(distance + 1);
+                    queue.push_back((x, y));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[doc = "Represents a chess piece with a color and a kind. Pieces are small and Copy, so they are
+passed and stored by value."]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Piece {
     pub color: Color,
     pub kind: PieceKind,
     pub position: (usize, usize),
 }
 
+#[doc = "Pieces are ordered by position in row-major order, then by color and then by kind."]
+impl Ord for Piece {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.position, self.color, self.kind).cmp(&(other.position, other.color, other.kind))
+    }
+}
+
+impl PartialOrd for Piece {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Piece {
+    #[doc = "Creates a piece, checking that its position is on a default 8x8 board.
+    Returns an InvalidPosition error otherwise."]
+    pub fn new(
+        color: Color,
+        kind: PieceKind,
+        position: (usize, usize),
+    ) -> Result<Self, BoardError> {
+        let (x, y) = position;
+        if x >= 8 || y >= 8 {
+            return Err(BoardError::InvalidPosition);
+        }
+        Ok(Piece {
+            color,
+            kind,
+            position,
+        })
+    }
     #[doc = "Returns the color of the piece."]
     pub fn color(&self) -> Color {
         self.color
     }
+    #[doc = "Returns true if the piece is white."]
+    pub fn is_white(&self) -> bool {
+        self.color == Color::White
+    }
+    #[doc = "Returns true if the piece is black."]
+    pub fn is_black(&self) -> bool {
+        self.color == Color::Black
+    }
     #[doc = "Returns the position of the piece."]
     pub fn get_position(&self) -> (usize, usize) {
         self.position
@@ -59,23 +214,36 @@ impl Piece {
     returns, A Result containing a PieceKind enum variant if the character represents a valid chess piece.
     An InvalidPieceKind error if the character does not represent a valid chess piece."]
     pub fn from_char(c: char, position: (usize, usize)) -> Result<Option<Self>, PieceError> {
-        if c == '_' {
+        Self::from_char_with(c, position, Notation::Spanish)
+    }
+
+    #[doc = "Creates a Piece from the character c written in the given notation, like from_char does for Spanish."]
+    pub fn from_char_with(
+        c: char,
+        position: (usize, usize),
+        notation: Notation,
+    ) -> Result<Option<Self>, PieceError> {
+        if EMPTY_SQUARE_CHARS.contains(&c) {
             return Ok(None);
         }
 
-        let color = if c.is_lowercase() {
-            Color::White
-        } else {
-            Color::Black
+        let color = match (notation, c.is_lowercase()) {
+            (Notation::Fen, true) | (Notation::Spanish | Notation::English, false) => Color::Black,
+            (Notation::Fen, false) | (Notation::Spanish | Notation::English, true) => Color::White,
         };
 
-        let kind = match c.to_ascii_uppercase() {
-            'R' => PieceKind::King,
-            'D' => PieceKind::Queen,
-            'A' => PieceKind::Bishop,
-            'C' => PieceKind::Knight,
-            'T' => PieceKind::Rook,
-            'P' => PieceKind::Pawn,
+        let kind = match (notation, c.to_ascii_uppercase()) {
+            (Notation::Spanish, 'R') => PieceKind::King,
+            (Notation::Spanish, 'D') => PieceKind::Queen,
+            (Notation::Spanish, 'A') => PieceKind::Bishop,
+            (Notation::Spanish, 'C') => PieceKind::Knight,
+            (Notation::Spanish, 'T') => PieceKind::Rook,
+            (Notation::English | Notation::Fen, 'K') => PieceKind::King,
+            (Notation::English | Notation::Fen, 'Q') => PieceKind::Queen,
+            (Notation::English | Notation::Fen, 'B') => PieceKind::Bishop,
+            (Notation::English | Notation::Fen, 'N') => PieceKind::Knight,
+            (Notation::English | Notation::Fen, 'R') => PieceKind::Rook,
+            (_, 'P') => PieceKind::Pawn,
             _ => return Err(PieceError::InvalidPieceKind(c)),
         };
 
@@ -86,6 +254,40 @@ impl Piece {
         }))
     }
 
+    #[doc = "Returns the character that represents the piece, the inverse of from_char.
+    Black pieces are uppercase and white pieces are lowercase."]
+    pub fn to_char(&self) -> char {
+        let c = match self.kind {
+            PieceKind::King => 'R',
+            PieceKind::Queen => 'D',
+            PieceKind::Bishop => 'A',
+            PieceKind::Knight => 'C',
+            PieceKind::Rook => 'T',
+            PieceKind::Pawn => 'P',
+        };
+        match self.color {
+            Color::White => c.to_ascii_lowercase(),
+            Color::Black => c,
+        }
+    }
+
+    #[doc = "Returns the character that represents the piece in FEN, using English letters
+    with white pieces in uppercase and black pieces in lowercase."]
+    pub fn fen_char(&self) -> char {
+        let c = match self.kind {
+            PieceKind::King => 'K',
+            PieceKind::Queen => 'Q',
+            PieceKind::Bishop => 'B',
+            PieceKind::Knight => 'N',
+            PieceKind::Rook => 'R',
+            PieceKind::Pawn => 'P',
+        };
+        match self.color {
+            Color::White => c,
+            Color::Black => c.to_ascii_lowercase(),
+        }
+    }
+
     fn capture_piece_at(&self, position: (usize, usize)) -> bool {
         match self.kind {
             PieceKind::Rook => self.capture_with_rook(position),
@@ -93,14 +295,138 @@ impl Piece {
             PieceKind::Knight => self.capture_with_knight(position),
             PieceKind::Bishop => self.capture_with_bishop(position),
             PieceKind::Queen => self.capture_with_queen(position),
-            PieceKind::Pawn => self.capture_with_pawn(position),
+            PieceKind::Pawn => self.capture_with_pawn(position, Orientation::default()),
+        }
+    }
+    #[doc = "Returns the Unicode chess symbol of the piece, outlined for white pieces and filled for black ones."]
+    pub fn glyph(&self) -> char {
+        match (self.color, self.kind) {
+            (Color::White, PieceKind::King) => '♔',
+            (Color::White, PieceKind::Queen) => '♕',
+            (Color::White, PieceKind::Rook) => '♖',
+            (Color::White, PieceKind::Bishop) => '♗',
+            (Color::White, PieceKind::Knight) => '♘',
+            (Color::White, PieceKind::Pawn) => '♙',
+            (Color::Black, PieceKind::King) => '♚',
+            (Color::Black, PieceKind::Queen) => '♛',
+            (Color::Black, PieceKind::Rook) => '♜',
+            (Color::Black, PieceKind::Bishop) => '♝',
+            (Color::Black, PieceKind::Knight) => '♞',
+            (Color::Black, PieceKind::Pawn) => '♟',
         }
     }
+
     #[doc = "Checks if the piece can capture another piece at the specified position.
+    Only the geometry is checked, use Board::can_capture to also check the colors and blockers.
     parameters, position A tuple representing the position of the piece to capture.
     return, true if the piece can capture another piece at the specified position, false otherwise."]
     pub fn can_capture(&self, position: (usize, usize)) -> bool {
-        self.capture_piece_at(position)
+        self.can_capture_oriented(position, Orientation::default())
+    }
+
+    #[doc = "Same as can_capture, with pawns advancing in the given orientation."]
+    pub fn can_capture_oriented(&self, position: (usize, usize), orientation: Orientation) -> bool {
+        match self.kind {
+            PieceKind::Pawn => self.capture_with_pawn(position, orientation),
+            _ => self.capture_piece_at(position),
+        }
+    }
+
+    #[doc = "Checks if the piece can move to the specified empty position.
+    Pawns advance one square forward, every other piece moves the same way it captures.
+    parameters, position A tuple representing the position to move to.
+    return, true if the piece can move to the specified position, false otherwise."]
+    pub fn can_move_to(&self, position: (usize, usize)) -> bool {
+        self.can_move_to_oriented(position, Orientation::default())
+    }
+
+    #[doc = "Same as can_move_to, with pawns advancing in the given orientation."]
+    pub fn can_move_to_oriented(&self, position: (usize, usize), orientation: Orientation) -> bool {
+        match self.kind {
+            PieceKind::Pawn => self.move_with_pawn(position, orientation),
+            _ => self.capture_piece_at(position),
+        }
+    }
+
+    #[doc = "Returns true if the piece is a pawn standing on the last rank it advances towards,
+    the highest column for white pawns and the lowest for black ones."]
+    pub fn is_on_promotion_square(&self, board_size: usize) -> bool {
+        self.is_on_promotion_square_oriented(board_size, Orientation::default())
+    }
+
+    #[doc = "Same as is_on_promotion_square, with pawns advancing in the given orientation.
+    The promotion square is the one a pawn can't advance from without leaving the board."]
+    pub fn is_on_promotion_square_oriented(
+        &self,
+        board_size: usize,
+        orientation: Orientation,
+    ) -> bool {
+        self.kind == PieceKind::Pawn && self.pawn_step(0, board_size, orientation).is_none()
+    }
+
+    #[doc = "Promotes a pawn to the given kind.
+    Only pawns can be promoted, and never to a king or a pawn. Otherwise an InvalidPromotion error is returned."]
+    pub fn promote(&mut self, to: PieceKind) -> Result<(), BoardError> {
+        match (self.kind, to) {
+            (PieceKind::Pawn, PieceKind::King | PieceKind::Pawn) => {
+                Err(BoardError::InvalidPromotion)
+            }
+            (PieceKind::Pawn, kind) => {
+                self.kind = kind;
+                Ok(())
+            }
+            _ => Err(BoardError::InvalidPromotion),
+        }
+    }
+
+    #[doc = "Returns every position the piece can move to in one move on an empty board_size x board_size board,
+    in row-major order."]
+    pub fn reachable_squares(&self, board_size: usize) -> Vec<(usize, usize)> {
+        (0..board_size)
+            .flat_map(|x| (0..board_size).map(move |y| (x, y)))
+            .filter(|&position| position != self.position && self.can_move_to(position))
+            .collect()
+    }
+
+    #[doc = "Returns the square a pawn reaches by stepping one square forward and the given number of squares
+    sideways, or None if the step leaves a board_size x board_size board. The bounds are checked before
+    the position is computed, so a pawn on the first or last file never wraps around to the other side."]
+    fn pawn_step(
+        &self,
+        sideways: i32,
+        board_size: usize,
+        orientation: Orientation,
+    ) -> Option<(usize, usize)> {
+        let (dx, dy) = orientation.pawn_direction(self.color);
+        let x = self
+            .position
+            .0
+            .checked_add_signed((dx + sideways * dy.abs()) as isize)?;
+        let y = self
+            .position
+            .1
+            .checked_add_signed((dy + sideways * dx.abs()) as isize)?;
+        (x < board_size && y < board_size).then_some((x, y))
+    }
+
+    #[doc = "Returns the squares a pawn attacks on a board_size x board_size board, in row-major order.
+    A pawn on the edge of the board has a single diagonal, and other pieces attack no squares this way."]
+    pub fn pawn_capture_squares(
+        &self,
+        board_size: usize,
+        orientation: Orientation,
+    ) -> Vec<(usize, usize)> {
+        if self.kind != PieceKind::Pawn {
+            return Vec::new();
+        }
+        [-1, 1]
+            .into_iter()
+            .filter_map(|sideways| self.pawn_step(sideways, board_size, orientation))
+            .collect()
+    }
+
+    fn move_with_pawn(&self, target_position: (usize, usize), orientation: Orientation) -> bool {
+        self.pawn_step(0, usize::MAX, orientation) == Some(target_position)
     }
 
     fn capture_with_knight(&self, target_position: (usize, usize)) -> bool {
@@ -112,6 +438,25 @@ impl Piece {
         (x_diff == 1 && y_diff == 2) || (x_diff == 2 && y_diff == 1)
     }
 
+    #[doc = "Returns true unless the piece is a rook, bishop or queen and the target is more than max_range squares away.
+    A max_range of None means sliding pieces reach any distance, as in regular chess."]
+    pub fn is_within_range(
+        &self,
+        target_position: (usize, usize),
+        max_range: Option<usize>,
+    ) -> bool {
+        let sliding = matches!(
+            self.kind,
+            PieceKind::Rook | PieceKind::Bishop | PieceKind::Queen
+        );
+        let distance = self
+            .position
+            .0
+            .abs_diff(target_position.0)
+            .max(self.position.1.abs_diff(target_position.1));
+        !sliding || max_range.is_none_or(|range| distance <= range)
+    }
+
     fn capture_with_rook(&self, target_position: (usize, usize)) -> bool {
         // Rooks can only move along a row or a column, not diagonally
         if self.position.0 != target_position.0 && self.position.1 != target_position.1 {
@@ -145,19 +490,10 @@ impl Piece {
         self.capture_with_rook(target_position) || self.capture_with_bishop(target_position)
     }
 
-    fn capture_with_pawn(&self, target_position: (usize, usize)) -> bool {
-        let (x1, y1) = self.position;
-        let (x2, y2) = target_position;
-
-        let x_diff = (x1 as i32 - x2 as i32).abs();
-        let y_diff = (y1 as i32 - y2 as i32).abs();
-
-        let is_diagonal_distance = x_diff == 1 && y_diff == 1;
-        let is_valid_direction = match self.color {
-            Color::White => y2 > y1,
-            Color::Black => y2 < y1,
-        };
-        is_diagonal_distance && is_valid_direction
+    fn capture_with_pawn(&self, target_position: (usize, usize), orientation: Orientation) -> bool {
+        [-1, 1].into_iter().any(|sideways| {
+            self.pawn_step(sideways, usize::MAX, orientation) == Some(target_position)
+        })
     }
 }
 
@@ -165,6 +501,46 @@ impl Piece {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_white_and_is_black() {
+        let white = Piece {
+            color: Color::White,
+            kind: PieceKind::Pawn,
+            position: (0, 0),
+        };
+        let black = Piece {
+            color: Color::Black,
+            ..white
+        };
+        assert!(white.is_white());
+        assert!(!white.is_black());
+        assert!(black.is_black());
+        assert!(!black.is_white());
+    }
+
+    #[test]
+    fn test_piece_ordering() {
+        let king = Piece {
+            color: Color::Black,
+            kind: PieceKind::King,
+            position: (0, 5),
+        };
+        let rook = Piece {
+            color: Color::White,
+            kind: PieceKind::Rook,
+            position: (1, 0),
+        };
+        let white_king = Piece {
+            color: Color::White,
+            ..king
+        };
+        assert!(king < rook);
+        assert!(white_king < king);
+        let mut pieces = vec![rook, king, white_king];
+        pieces.sort();
+        assert_eq!(pieces, vec![white_king, king, rook]);
+    }
+
     #[test]
     fn test_king_can_capture() {
         let king = Piece {
@@ -255,6 +631,104 @@ mod tests {
         assert!(!black_pawn.capture_piece_at((2, 5)));
     }
 
+    #[test]
+    fn test_to_char_round_trips_from_char() {
+        for c in ['R', 'D', 'A', 'C', 'T', 'P', 'r', 'd', 'a', 'c', 't', 'p'] {
+            let piece = Piece::from_char(c, (0, 0)).unwrap().unwrap();
+            assert_eq!(piece.to_char(), c);
+        }
+    }
+
+    #[test]
+    fn test_fen_char() {
+        let piece = Piece::from_char('t', (0, 0)).unwrap().unwrap();
+        assert_eq!(piece.fen_char(), 'R');
+        let piece = Piece::from_char('C', (0, 0)).unwrap().unwrap();
+        assert_eq!(piece.fen_char(), 'n');
+    }
+
+    #[test]
+    fn test_pawn_can_move() {
+        let white_pawn = Piece {
+            color: Color::White,
+            kind: PieceKind::Pawn,
+            position: (2, 1),
+        };
+        assert!(white_pawn.can_move_to((2, 2)));
+        assert!(!white_pawn.can_move_to((2, 0)));
+        assert!(!white_pawn.can_move_to((3, 2)));
+
+        let black_pawn = Piece {
+            color: Color::Black,
+            kind: PieceKind::Pawn,
+            position: (2, 6),
+        };
+        assert!(black_pawn.can_move_to((2, 5)));
+        assert!(!black_pawn.can_move_to((2, 7)));
+    }
+
+    #[test]
+    fn test_reachable_squares() {
+        let king = Piece {
+            color: Color::White,
+            kind: PieceKind::King,
+            position: (0, 0),
+        };
+        assert_eq!(king.reachable_squares(8), vec![(0, 1), (1, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_knight_move_distance() {
+        let knight = PieceKind::Knight;
+        assert_eq!(knight.move_distance((0, 0), (0, 0), 8), Some(0));
+        assert_eq!(knight.move_distance((0, 0), (1, 2), 8), Some(1));
+        assert_eq!(knight.move_distance((0, 0), (1, 1), 8), Some(4));
+        assert_eq!(knight.move_distance((0, 0), (7, 7), 8), Some(6));
+        assert_eq!(knight.move_distance((0, 0), (1, 1), 2), None);
+        assert_eq!(knight.move_distance((0, 0), (8, 8), 8), None);
+    }
+
+    #[test]
+    fn test_king_and_rook_move_distance() {
+        assert_eq!(PieceKind::King.move_distance((0, 0), (7, 3), 8), Some(7));
+        assert_eq!(PieceKind::King.move_distance((4, 4), (2, 5), 8), Some(2));
+        assert_eq!(PieceKind::Rook.move_distance((0, 0), (0, 5), 8), Some(1));
+        assert_eq!(PieceKind::Rook.move_distance((0, 0), (3, 5), 8), Some(2));
+        assert_eq!(PieceKind::Bishop.move_distance((0, 0), (0, 1), 8), None);
+        assert_eq!(PieceKind::Bishop.move_distance((0, 0), (0, 2), 8), Some(2));
+    }
+
+    #[test]
+    fn test_promote() {
+        let mut pawn = Piece {
+            color: Color::White,
+            kind: PieceKind::Pawn,
+            position: (0, 7),
+        };
+        assert!(pawn.is_on_promotion_square(8));
+        assert_eq!(
+            pawn.promote(PieceKind::King),
+            Err(BoardError::InvalidPromotion)
+        );
+        assert_eq!(
+            pawn.promote(PieceKind::Pawn),
+            Err(BoardError::InvalidPromotion)
+        );
+        assert_eq!(pawn.promote(PieceKind::Queen), Ok(()));
+        assert_eq!(pawn.kind, PieceKind::Queen);
+        assert_eq!(
+            pawn.promote(PieceKind::Rook),
+            Err(BoardError::InvalidPromotion)
+        );
+
+        let black_pawn = Piece {
+            color: Color::Black,
+            kind: PieceKind::Pawn,
+            position: (0, 7),
+        };
+        assert!(!black_pawn.is_on_promotion_square(8));
+    }
+
     #[test]
     fn test_invalid_character_for_piece() {
         let result = Piece::from_char('X', (0, 0));
@@ -269,6 +743,8 @@ mod tests {
     fn test_piece_from_char_underscore() {
         let piece = Piece::from_char('_', (0, 0)).unwrap();
         assert_eq!(piece, None);
+        let piece = Piece::from_char('.', (0, 0)).unwrap();
+        assert_eq!(piece, None);
     }
 
     #[test]
@@ -297,4 +773,131 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().unwrap().kind, PieceKind::Pawn);
     }
+
+    #[test]
+    fn test_vertical_pawns_advance_along_rows() {
+        let white = Piece {
+            color: Color::White,
+            kind: PieceKind::Pawn,
+            position: (4, 4),
+        };
+        let black = Piece {
+            color: Color::Black,
+            ..white
+        };
+        let vertical = Orientation::Vertical;
+        assert!(white.can_move_to_oriented((3, 4), vertical));
+        assert!(!white.can_move_to_oriented((4, 5), vertical));
+        assert!(white.can_capture_oriented((3, 3), vertical));
+        assert!(!white.can_capture_oriented((5, 5), vertical));
+        assert!(black.can_move_to_oriented((5, 4), vertical));
+        assert!(black.can_capture_oriented((5, 3), vertical));
+        assert!(!black.can_capture_oriented((3, 5), vertical));
+        assert!(!white.is_on_promotion_square_oriented(8, vertical));
+        assert!(Piece {
+            position: (0, 4),
+            ..white
+        }
+        .is_on_promotion_square_oriented(8, vertical));
+    }
+
+    #[test]
+    fn test_piece_kind_value() {
+        assert_eq!(PieceKind::Pawn.value(), 1);
+        assert_eq!(PieceKind::Knight.value(), PieceKind::Bishop.value());
+        assert_eq!(PieceKind::Rook.value(), 5);
+        assert_eq!(PieceKind::Queen.value(), 9);
+        assert_eq!(PieceKind::King.value(), 0);
+    }
+
+    #[test]
+    fn test_new_validates_the_position() {
+        assert_eq!(
+            Piece::new(Color::White, PieceKind::Rook, (7, 0)),
+            Ok(Piece {
+                color: Color::White,
+                kind: PieceKind::Rook,
+                position: (7, 0),
+            })
+        );
+        assert_eq!(
+            Piece::new(Color::Black, PieceKind::Queen, (8, 0)),
+            Err(BoardError::InvalidPosition)
+        );
+        assert_eq!(
+            Piece::new(Color::Black, PieceKind::Queen, (0, 8)),
+            Err(BoardError::InvalidPosition)
+        );
+    }
+
+    #[test]
+    fn test_glyph() {
+        let king = Piece {
+            color: Color::White,
+            kind: PieceKind::King,
+            position: (0, 0),
+        };
+        let queen = Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (0, 0),
+        };
+        assert_eq!(king.glyph(), '♔');
+        assert_eq!(queen.glyph(), '♛');
+    }
+
+    #[test]
+    fn test_color_opposite() {
+        assert_eq!(Color::White.opposite(), Color::Black);
+        assert_eq!(Color::Black.opposite(), Color::White);
+    }
+
+    #[test]
+    fn test_pawns_on_the_edge_files_have_one_diagonal() {
+        let pawn = |color, position| Piece {
+            color,
+            kind: PieceKind::Pawn,
+            position,
+        };
+        let vertical = Orientation::Vertical;
+        assert_eq!(
+            pawn(Color::White, (4, 0)).pawn_capture_squares(8, vertical),
+            vec![(3, 1)]
+        );
+        assert_eq!(
+            pawn(Color::White, (4, 7)).pawn_capture_squares(8, vertical),
+            vec![(3, 6)]
+        );
+        assert_eq!(
+            pawn(Color::Black, (4, 0)).pawn_capture_squares(8, vertical),
+            vec![(5, 1)]
+        );
+        assert_eq!(
+            pawn(Color::Black, (4, 7)).pawn_capture_squares(8, vertical),
+            vec![(5, 6)]
+        );
+        assert!(!pawn(Color::White, (4, 0)).can_capture_oriented((3, 7), vertical));
+        assert!(!pawn(Color::Black, (4, 7)).can_capture_oriented((5, 0), vertical));
+
+        // Horizontal pawns advance along their row, so the a- and h-files are where they start or promote.
+        let horizontal = Orientation::Horizontal;
+        assert_eq!(
+            pawn(Color::White, (4, 0)).pawn_capture_squares(8, horizontal),
+            vec![(3, 1), (5, 1)]
+        );
+        assert!(pawn(Color::White, (4, 7))
+            .pawn_capture_squares(8, horizontal)
+            .is_empty());
+        assert!(pawn(Color::Black, (4, 0))
+            .pawn_capture_squares(8, horizontal)
+            .is_empty());
+        assert_eq!(
+            pawn(Color::Black, (4, 7)).pawn_capture_squares(8, horizontal),
+            vec![(3, 6), (5, 6)]
+        );
+        assert_eq!(
+            pawn(Color::White, (0, 0)).pawn_capture_squares(8, horizontal),
+            vec![(1, 1)]
+        );
+    }
 }
@@ -77,3 +77,26 @@ fn test_white_queen_and_black_pawn_results_in_both_lose() -> Result<(), Box<dyn
     assert_eq!(winner, None);
     Ok(())
 }
+
+#[test]
+fn test_sorted_pieces_are_deterministic() -> Result<(), Box<dyn Error>> {
+    let board = Board::builder()
+        .piece(Color::White, PieceKind::Rook, (5, 6))
+        .piece(Color::Black, PieceKind::Queen, (2, 3))
+        .piece(Color::White, PieceKind::King, (2, 1))
+        .build()?;
+    let same = Board::builder()
+        .piece(Color::White, PieceKind::King, (2, 1))
+        .piece(Color::White, PieceKind::Rook, (5, 6))
+        .piece(Color::Black, PieceKind::Queen, (2, 3))
+        .build()?;
+
+    let positions = board
+        .sorted_pieces()
+        .iter()
+        .map(|piece| piece.get_position())
+        .collect::<Vec<(usize, usize)>>();
+    assert_eq!(positions, vec![(2, 1), (2, 3), (5, 6)]);
+    assert_eq!(board.sorted_pieces(), same.sorted_pieces());
+    Ok(())
+}
@@ -1,5 +1,7 @@
+#![allow(dead_code)]
+
 use std::error::Error;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Write};
 use std::process::{Command, ExitStatus, Stdio};
 
 #[doc = "struct represents the output of a command, including its standard output, standard error, and exit status."]
@@ -10,21 +12,30 @@ pub struct CommandOutput {
 }
 
 impl CommandOutput {
-    pub fn run(mut command: Command) -> Result<Self, Box<dyn Error>> {
-        let child = command
+    pub fn run(command: Command) -> Result<Self, Box<dyn Error>> {
+        Self::run_with_stdin(command, "")
+    }
+
+    #[doc = "Runs the command writing the given input to its standard input."]
+    pub fn run_with_stdin(mut command: Command, input: &str) -> Result<Self, Box<dyn Error>> {
+        let mut child = command
+            .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
             .unwrap();
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).unwrap();
+        }
         let output = child.wait_with_output().unwrap();
         let stdout = BufReader::new(output.stdout.as_slice());
         let stderr = BufReader::new(output.stderr.as_slice());
         let mut stdout_str = String::new();
         let mut stderr_str = String::new();
-        for line in stdout.lines().flatten() {
+        for line in stdout.lines().map_while(Result::ok) {
             stdout_str.push_str(&line);
         }
-        for line in stderr.lines().flatten() {
+        for line in stderr.lines().map_while(Result::ok) {
             stderr_str.push_str(&line);
         }
         Ok(Self {
@@ -41,6 +52,25 @@ impl CommandOutput {
         cmd
     }
 
+    #[doc = "Creates a new Command that will run the program with the given arguments."]
+    pub fn cargo_run_args(args: &[&str]) -> Command {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run").arg("--quiet").arg("--").args(args);
+        cmd
+    }
+
+    #[doc = "Creates a new Command that will run the given binary of the package with the given arguments."]
+    pub fn cargo_run_bin(bin: &str, args: &[&str]) -> Command {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run")
+            .arg("--quiet")
+            .arg("--bin")
+            .arg(bin)
+            .arg("--")
+            .args(args);
+        cmd
+    }
+
     pub fn stdout(&self) -> Option<&str> {
         if self.stdout.is_empty() {
             None
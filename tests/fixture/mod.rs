@@ -0,0 +1,33 @@
+#![allow(dead_code)]
+
+use rustmate::board::Board;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[doc = "returns the text of a board file for a given board, written with its Display implementation so that
+fixtures always match what the parser reads. Boards that aren't 8x8 get a size header."]
+pub fn fixture_text(board: &Board) -> String {
+    if board.size() == 8 {
+        board.to_string()
+    } else {
+        format!("size: {}\n{}", board.size(), board)
+    }
+}
+
+#[doc = "writes a board as the fixture file dir/name and returns its path."]
+pub fn write_fixture(dir: &Path, name: &str, board: &Board) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    fs::write(&path, fixture_text(board))?;
+    Ok(path)
+}
+
+#[doc = "writes every (name, board) pair as a fixture file in dir, regenerating them all at once,
+and returns their paths in the same order."]
+pub fn write_fixtures(dir: &Path, fixtures: &[(&str, Board)]) -> io::Result<Vec<PathBuf>> {
+    fixtures
+        .iter()
+        .map(|(name, board)| write_fixture(dir, name, board))
+        .collect()
+}
@@ -54,6 +54,49 @@ fn test_result_in_p() {
     }
 }
 
+#[test]
+fn test_result_in_p_for_empty_board() {
+    let cmd = CommandOutput::cargo_run("tests/fixtures/ejemplo05.txt");
+    let cmd_assert = CommandOutput::run(cmd).unwrap();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(stdout, "P");
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
+
+#[test]
+fn test_show_board_prints_grid_before_result() {
+    let cmd = CommandOutput::cargo_run_args(&["--show-board", "tests/fixtures/ejemplo01.txt"]);
+    let cmd_assert = CommandOutput::run(cmd).unwrap();
+    let grid = std::fs::read_to_string("tests/fixtures/ejemplo01.txt")
+        .unwrap()
+        .lines()
+        .collect::<String>();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(stdout, format!("{}N", grid));
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
+
+#[test]
+fn test_json_output() {
+    let cmd = CommandOutput::cargo_run_args(&["--json", "tests/fixtures/ejemplo01.txt"]);
+    let cmd_assert = CommandOutput::run(cmd).unwrap();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(stdout, r#"{"result":"N","winner":"Black"}"#);
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
+
 #[test]
 fn test_expected_error_format_for_invalid_character() {
     let cmd = CommandOutput::cargo_run("tests/fixtures/ejemplo_errors_01.txt");
@@ -61,7 +104,10 @@ fn test_expected_error_format_for_invalid_character() {
 
     if let Some(stderr) = cmd_assert.stderr() {
         assert!(cmd_assert.failure());
-        assert_eq!(stderr, "ERROR: [Invalid piece kind character: X]");
+        assert_eq!(
+            stderr,
+            "ERROR: [Invalid piece kind character: X at row 7, column 6]"
+        );
     } else {
         println!("Test skipped: stderr of cargo child process output is empty");
     }
@@ -90,3 +136,49 @@ fn test_expected_error_format_for_file_not_exists() {
         println!("Test skipped: stderr of cargo child process output is empty");
     }
 }
+
+#[test]
+fn test_tally_over_several_files() {
+    let cmd = CommandOutput::cargo_run_args(&[
+        "--tally",
+        "tests/fixtures/ejemplo01.txt",
+        "tests/fixtures/ejemplo02.txt",
+        "tests/fixtures/ejemplo03.txt",
+        "tests/fixtures/ejemplo04.txt",
+        "tests/fixtures/ejemplo05.txt",
+        "tests/fixtures/ejemplo_errors_01.txt",
+        "not_existing_test.txt",
+    ]);
+    let cmd_assert = CommandOutput::run(cmd).unwrap();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(stdout, "B: 1 N: 1 E: 1 P: 2 ERROR: 2");
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
+
+#[test]
+fn test_directory_of_board_files() {
+    let cmd = CommandOutput::cargo_run_args(&["tests/fixtures"]);
+    let cmd_assert = CommandOutput::run(cmd).unwrap();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        for line in [
+            "ejemplo01.txt: N",
+            "ejemplo02.txt: B",
+            "ejemplo03.txt: E",
+            "ejemplo04.txt: P",
+        ] {
+            assert!(stdout.contains(line), "missing {:?} in {:?}", line, stdout);
+        }
+        assert!(!stdout.contains("ejemplo_errors_01.txt"));
+        assert!(cmd_assert
+            .stderr()
+            .is_some_and(|stderr| stderr.contains("ejemplo_errors_01.txt: ERROR")));
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
@@ -1,6 +1,8 @@
-use rustmate::board::Board;
-use rustmate::parser;
-use rustmate::piece::{Color, Piece, PieceKind};
+mod fixture;
+
+use rustmate::board::{Board, BoardError, BoardSummary};
+use rustmate::parser::{self, ParserError};
+use rustmate::piece::{Color, Notation, Piece, PieceError, PieceKind};
 use std::error::Error;
 
 #[test]
@@ -78,3 +80,507 @@ fn test_black_pawn_and_white_queen_in_file() -> Result<(), Box<dyn Error>> {
     assert_eq!(board, expected_board);
     Ok(())
 }
+
+#[test]
+fn test_builder_matches_file() -> Result<(), Box<dyn Error>> {
+    let expected_board = Board::builder()
+        .piece(Color::Black, PieceKind::Queen, (2, 3))
+        .piece(Color::White, PieceKind::Rook, (5, 6))
+        .build()?;
+
+    let board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(board, expected_board);
+    Ok(())
+}
+
+#[test]
+fn test_multiple_boards_in_file() -> Result<(), Box<dyn Error>> {
+    let boards = parser::from_path_multi("tests/fixtures/ejemplo_multi.txt")?;
+    assert_eq!(boards.len(), 2);
+    assert_eq!(
+        boards[0],
+        parser::from_path("tests/fixtures/ejemplo01.txt")?
+    );
+    assert_eq!(
+        boards[1],
+        parser::from_path("tests/fixtures/ejemplo02.txt")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_multiple_boards_in_file_reports_block_index() {
+    let result = parser::from_path_multi("tests/fixtures/ejemplo_multi_errors.txt");
+    assert_eq!(
+        result,
+        Err(ParserError::InBlock {
+            index: 1,
+            error: Box::new(ParserError::At {
+                row: 7,
+                col: 6,
+                error: Box::new(ParserError::InvalidPiece('X')),
+            }),
+        })
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Block 1: Invalid piece kind character: X at row 7, column 6"
+    );
+}
+
+#[test]
+fn test_dots_as_empty_squares_in_file() -> Result<(), Box<dyn Error>> {
+    let expected_board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+
+    let board = parser::from_path("tests/fixtures/ejemplo01_dots.txt")?;
+    assert_eq!(board, expected_board);
+    Ok(())
+}
+
+#[test]
+fn test_size_header_in_file() -> Result<(), Box<dyn Error>> {
+    let expected_board = Board::builder()
+        .size(6)
+        .piece(Color::Black, PieceKind::Queen, (1, 2))
+        .piece(Color::White, PieceKind::Rook, (4, 4))
+        .build()?;
+
+    let board = parser::from_path("tests/fixtures/ejemplo_6x6.txt")?;
+    assert_eq!(board.size(), 6);
+    assert_eq!(board, expected_board);
+    Ok(())
+}
+
+#[test]
+fn test_size_header_validates_rows() {
+    let result = parser::from_string("size: 6\n_ _ _ _ _ _ _ _\n");
+    assert_eq!(result, Err(ParserError::InvalidBoardSize));
+    let result = parser::from_string("size: six\n_ _ _ _ _ _\n");
+    assert_eq!(result, Err(ParserError::InvalidBoardSize));
+    let result = parser::from_string("size: 0\n");
+    assert_eq!(result, Err(ParserError::InvalidBoardSize));
+    let result = parser::from_string(&format!("size: 27\n{}", "_ ".repeat(27)));
+    assert_eq!(result, Err(ParserError::InvalidBoardSize));
+}
+
+#[test]
+fn test_fen_placement_of_fixture() -> Result<(), Box<dyn Error>> {
+    let board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(board.fen_placement(), "8/8/3q4/8/8/6R1/8/8");
+    assert!(board.to_fen().starts_with(&board.fen_placement()));
+    Ok(())
+}
+
+#[test]
+fn test_validate_all_reports_every_invalid_piece() -> Result<(), Box<dyn Error>> {
+    let input = std::fs::read_to_string("tests/fixtures/ejemplo_errors_03.txt")?;
+    let errors = parser::validate_all(&input);
+    assert_eq!(
+        errors,
+        vec![
+            ParserError::At {
+                row: 3,
+                col: 5,
+                error: Box::new(ParserError::InvalidPiece('Z')),
+            },
+            ParserError::At {
+                row: 7,
+                col: 1,
+                error: Box::new(ParserError::InvalidPiece('X')),
+            },
+        ]
+    );
+    assert_eq!(
+        parser::from_path("tests/fixtures/ejemplo_errors_03.txt"),
+        Err(ParserError::At {
+            row: 3,
+            col: 5,
+            error: Box::new(ParserError::InvalidPiece('Z')),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_validate_all_reports_row_and_size_errors() {
+    let input = "_ _ _ _ _ _ _ _\n_ _ _ _ _ _ _\n_ _ _ _ _ _ _ _\n";
+    let errors = parser::validate_all(input);
+    assert_eq!(
+        errors,
+        vec![
+            ParserError::RaggedBoard {
+                expected: 8,
+                found: 7,
+                row: 1,
+            },
+            ParserError::InvalidBoardSize,
+        ]
+    );
+    assert!(parser::validate_all(&"_ _ _ _ _ _ _ _\n".repeat(8)).is_empty());
+}
+
+#[test]
+fn test_board_try_from_and_parse() -> Result<(), Box<dyn Error>> {
+    let input = std::fs::read_to_string("tests/fixtures/ejemplo01.txt")?;
+    let expected_board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+
+    let board = Board::try_from(input.as_str())?;
+    assert_eq!(board, expected_board);
+    let board = input.parse::<Board>()?;
+    assert_eq!(board, expected_board);
+
+    assert_eq!(
+        "_ _ _\n".parse::<Board>(),
+        Err(ParserError::NotEnoughTokens)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_generated_fixtures_reparse() -> Result<(), Box<dyn Error>> {
+    let eight = Board::builder()
+        .piece(Color::Black, PieceKind::Queen, (2, 3))
+        .piece(Color::White, PieceKind::Rook, (5, 6))
+        .build()?;
+    let six = Board::builder()
+        .size(6)
+        .piece(Color::White, PieceKind::Knight, (0, 0))
+        .piece(Color::Black, PieceKind::Pawn, (5, 5))
+        .build()?;
+    let dir = std::env::temp_dir().join(format!("rustmate_fixtures_{}", std::process::id()));
+    let paths = fixture::write_fixtures(
+        &dir,
+        &[
+            ("generated_8x8.txt", eight.clone()),
+            ("generated_6x6.txt", six.clone()),
+        ],
+    )?;
+    let reparsed = paths
+        .iter()
+        .map(|path| parser::from_path(&path.to_string_lossy()))
+        .collect::<Result<Vec<Board>, ParserError>>();
+    std::fs::remove_dir_all(&dir)?;
+    assert_eq!(reparsed?, vec![eight.clone(), six]);
+    assert_eq!(
+        fixture::fixture_text(&eight),
+        std::fs::read_to_string("tests/fixtures/ejemplo01.txt")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_string_lossy() -> Result<(), Box<dyn Error>> {
+    let input = std::fs::read_to_string("tests/fixtures/ejemplo01.txt")?;
+    let typo = input.replacen("t", "x", 1);
+    assert_eq!(
+        parser::from_string(&typo),
+        Err(ParserError::At {
+            row: 5,
+            col: 6,
+            error: Box::new(ParserError::InvalidPiece('x')),
+        })
+    );
+
+    let (board, problems) = parser::from_string_lossy(&typo)?;
+    assert_eq!(problems, vec![(5, 6, 'x')]);
+    assert_eq!(board.piece_at((5, 6)), None);
+    assert_eq!(
+        board.piece_at((2, 3)),
+        Some(Piece {
+            color: Color::Black,
+            kind: PieceKind::Queen,
+            position: (2, 3),
+        })
+    );
+
+    assert_eq!(parser::from_string_lossy(&input)?.1, vec![]);
+    assert_eq!(
+        parser::from_string_lossy("_ _\n"),
+        Err(ParserError::NotEnoughTokens)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_explain_white_wins_fixture() -> Result<(), Box<dyn Error>> {
+    let board = parser::from_path("tests/fixtures/ejemplo02.txt")?;
+    assert_eq!(board.finish_game(), Board::RESULT_WHITE);
+    assert_eq!(
+        board.explain(),
+        "White's bishop on c2 can capture Black's pawn on g6, so White wins."
+    );
+    Ok(())
+}
+
+#[test]
+fn test_minimal_fen_of_fixture() -> Result<(), Box<dyn Error>> {
+    let mut board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(board.minimal_fen(), "8/8/3q4/8/8/6R1/8/8 w");
+    board.next_turn();
+    assert_eq!(board.minimal_fen(), "8/8/3q4/8/8/6R1/8/8 b");
+    Ok(())
+}
+
+#[test]
+fn test_piece_limits() -> Result<(), Box<dyn Error>> {
+    let input = "_ _ _ _ _ _ _ _\n"
+        .repeat(8)
+        .replacen("_ _ _ _ _ _ _ _", "d _ _ _ _ _ _ _", 1)
+        .replacen("_ _ _ _ _ _ _ _", "_ _ _ _ _ _ d D", 1);
+    let one_queen = parser::PieceLimits::new().with_limit(Color::White, PieceKind::Queen, 1);
+    assert_eq!(one_queen.limit(Color::White, PieceKind::Queen), Some(1));
+    assert_eq!(one_queen.limit(Color::Black, PieceKind::Queen), None);
+
+    let board = parser::from_string_with_limits(&input, &parser::PieceLimits::default())?;
+    assert_eq!(board, parser::from_string(&input)?);
+    let err = parser::from_string_with_limits(&input, &one_queen).unwrap_err();
+    assert_eq!(
+        err,
+        ParserError::At {
+            row: 1,
+            col: 6,
+            error: Box::new(ParserError::PieceLimitExceeded {
+                color: Color::White,
+                kind: PieceKind::Queen,
+                limit: 1,
+            }),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Too many White Queen pieces, at most 1 allowed at row 1, column 6"
+    );
+
+    let no_black_queen = parser::PieceLimits::new().with_limit(Color::Black, PieceKind::Queen, 0);
+    assert_eq!(
+        parser::from_path_with_limits("tests/fixtures/ejemplo01.txt", &no_black_queen),
+        Err(ParserError::At {
+            row: 2,
+            col: 3,
+            error: Box::new(ParserError::PieceLimitExceeded {
+                color: Color::Black,
+                kind: PieceKind::Queen,
+                limit: 0,
+            }),
+        })
+    );
+    assert_eq!(
+        parser::from_path_with_limits("tests/fixtures/ejemplo01.txt", &one_queen)?,
+        parser::from_path("tests/fixtures/ejemplo01.txt")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_path_with_notation() -> Result<(), Box<dyn Error>> {
+    let spanish = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(
+        parser::from_path_with("tests/fixtures/ejemplo01.txt", Notation::Spanish)?,
+        spanish
+    );
+    assert_eq!(
+        parser::from_path_with("tests/fixtures/ejemplo01_english.txt", Notation::English)?,
+        spanish
+    );
+    assert_eq!(
+        parser::from_path_with("tests/fixtures/ejemplo01_fen.txt", Notation::Fen)?,
+        spanish
+    );
+
+    // In Spanish the English rook is a king, and the queen isn't a piece at all.
+    assert_eq!(
+        parser::from_path("tests/fixtures/ejemplo01_english.txt"),
+        Err(ParserError::At {
+            row: 2,
+            col: 3,
+            error: Box::new(ParserError::InvalidPiece('Q')),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_rank_labeled_rows() -> Result<(), Box<dyn Error>> {
+    let labeled = parser::from_path("tests/fixtures/ejemplo01_labeled.txt")?;
+    let unlabeled = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(labeled, unlabeled);
+
+    let input = std::fs::read_to_string("tests/fixtures/ejemplo01_labeled.txt")?;
+    let swapped = input.replacen("8:", "7:", 1);
+    assert_eq!(
+        parser::from_string(&swapped),
+        Err(ParserError::InvalidRankLabel(0))
+    );
+    let missing = input.replacen("5:", "", 1);
+    assert_eq!(
+        parser::from_string(&missing),
+        Err(ParserError::InvalidRankLabel(3))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_records_of_fixture() -> Result<(), Box<dyn Error>> {
+    let board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(
+        board.to_records(),
+        "color,kind,row,col\nblack,queen,2,3\nwhite,rook,5,6\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_records_round_trip() -> Result<(), Box<dyn Error>> {
+    let board = parser::from_path("tests/fixtures/ejemplo02.txt")?;
+    let records = board.to_records();
+    assert_eq!(parser::from_records(&records)?, board);
+
+    let board = parser::from_path("tests/fixtures/ejemplo_6x6.txt")?;
+    let records = board.to_records();
+    assert!(records.starts_with("size: 6\ncolor,kind,row,col\n"));
+    assert_eq!(parser::from_records(&records)?, board);
+    let ten = Board::builder()
+        .size(10)
+        .piece(Color::Black, PieceKind::Knight, (9, 8))
+        .build()?;
+    assert_eq!(parser::from_records(&ten.to_records())?, ten);
+    Ok(())
+}
+
+#[test]
+fn test_from_records_rejects_malformed_rows() {
+    assert_eq!(
+        parser::from_records("color,kind,row,col\nwhite,wizard,2,3\n"),
+        Err(ParserError::InvalidRecord {
+            line: 2,
+            record: "white,wizard,2,3".to_string(),
+        })
+    );
+    assert_eq!(
+        parser::from_records("white,rook,2\n")
+            .unwrap_err()
+            .to_string(),
+        "Invalid record at line 1: white,rook,2"
+    );
+    assert_eq!(
+        parser::from_records("black,queen,8,0\n"),
+        Err(ParserError::InvalidPosition)
+    );
+}
+
+#[test]
+fn test_errors_share_the_boxed_error_path() {
+    fn assert_error<E: Error + Send + Sync + 'static>() {}
+    assert_error::<ParserError>();
+    assert_error::<BoardError>();
+    assert_error::<PieceError>();
+
+    fn from_piece() -> Result<(), Box<dyn Error + Send + Sync>> {
+        Piece::from_char('X', (0, 0))?;
+        Ok(())
+    }
+    fn from_parser() -> Result<(), Box<dyn Error + Send + Sync>> {
+        parser::from_string("_ X _ _ _ _ _ _\n")?;
+        Ok(())
+    }
+    assert_eq!(
+        from_piece().unwrap_err().to_string(),
+        "Invalid piece kind character: X"
+    );
+    assert_eq!(
+        from_parser().unwrap_err().to_string(),
+        "Invalid piece kind character: X at row 0, column 1"
+    );
+}
+
+#[test]
+fn test_rows_past_the_declared_size_are_trailing_content() {
+    let input = "size: 3\n_ _ _\n_ D _\n_ _ _\nt _ _\n";
+    assert_eq!(
+        parser::from_string(input),
+        Err(ParserError::TrailingContent(3))
+    );
+    assert_eq!(
+        parser::validate_all(input),
+        vec![ParserError::TrailingContent(3)]
+    );
+}
+
+#[test]
+fn test_summary_of_fixture() -> Result<(), Box<dyn Error>> {
+    let board = parser::from_path("tests/fixtures/ejemplo01.txt")?;
+    assert_eq!(
+        board.summary(),
+        BoardSummary {
+            white_pieces: 1,
+            black_pieces: 1,
+            material_balance: -4,
+            turn: Color::White,
+            result: 'N',
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_run_length_empty_squares() -> Result<(), Box<dyn Error>> {
+    let compressed = "8\n8\n3 D 4\n_ _ 6\n8\n6 t _\n4 _ _ 2\n8\n";
+    let board = parser::from_string(compressed)?;
+    assert_eq!(board, parser::from_path("tests/fixtures/ejemplo01.txt")?);
+
+    let board = parser::from_string("T 3 D 3\n8\n8\n8\n8\n8\n8\n8\n")?;
+    assert_eq!(
+        board
+            .rank(0)
+            .iter()
+            .map(|square| square.map(|piece| piece.kind))
+            .collect::<Vec<_>>(),
+        vec![
+            Some(PieceKind::Rook),
+            None,
+            None,
+            None,
+            Some(PieceKind::Queen),
+            None,
+            None,
+            None,
+        ]
+    );
+    assert_eq!(
+        parser::from_string("9\n8\n8\n8\n8\n8\n8\n8\n"),
+        Err(ParserError::InvalidBoardSize)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_is_game_over_for_fixtures() -> Result<(), Box<dyn Error>> {
+    assert!(parser::from_path("tests/fixtures/ejemplo02.txt")?.is_game_over());
+    assert!(!parser::from_path("tests/fixtures/ejemplo04.txt")?.is_game_over());
+    Ok(())
+}
+
+#[test]
+fn test_from_records_rejects_more_pieces_than_squares() {
+    let mut records = String::from("color,kind,row,col\n");
+    for i in 0..65 {
+        records.push_str(&format!("white,pawn,{},{}\n", i / 8, i % 8));
+    }
+    assert_eq!(
+        parser::from_records(&records),
+        Err(ParserError::TooManyPieces)
+    );
+}
+
+#[test]
+fn test_records_off_a_sized_board_are_invalid_positions() {
+    let records = "size: 6\ncolor,kind,row,col\nwhite,rook,2,6\n";
+    assert_eq!(
+        parser::from_records(records),
+        Err(ParserError::InvalidPosition)
+    );
+    assert_eq!(
+        parser::from_records(records).unwrap_err().to_string(),
+        "Invalid position"
+    );
+}
@@ -0,0 +1,85 @@
+use proptest::prelude::*;
+use rustmate::board::Board;
+use rustmate::piece::{Color, Piece, PieceKind};
+
+fn position() -> impl Strategy<Value = (usize, usize)> {
+    (0..8usize, 0..8usize)
+}
+
+fn color() -> impl Strategy<Value = Color> {
+    prop_oneof![Just(Color::White), Just(Color::Black)]
+}
+
+fn symmetric_kind() -> impl Strategy<Value = PieceKind> {
+    prop_oneof![
+        Just(PieceKind::Rook),
+        Just(PieceKind::Knight),
+        Just(PieceKind::King),
+        Just(PieceKind::Bishop),
+        Just(PieceKind::Queen),
+    ]
+}
+
+fn piece(color: Color, kind: PieceKind, position: (usize, usize)) -> Piece {
+    Piece {
+        color,
+        kind,
+        position,
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_capture_is_symmetric_for_non_pawns(
+        kind in symmetric_kind(),
+        color in color(),
+        a in position(),
+        b in position(),
+    ) {
+        prop_assert_eq!(
+            piece(color, kind, a).can_capture(b),
+            piece(color, kind, b).can_capture(a)
+        );
+    }
+
+    #[test]
+    fn test_board_capture_is_symmetric_for_non_pawns(
+        kind in symmetric_kind(),
+        a in position(),
+        b in position(),
+    ) {
+        prop_assume!(a != b);
+        let board = Board::builder()
+            .piece(Color::White, kind, a)
+            .piece(Color::Black, kind, b)
+            .build()
+            .unwrap();
+        prop_assert_eq!(board.can_capture(a, b), board.can_capture(b, a));
+    }
+
+    #[test]
+    fn test_pawns_only_capture_forward(color in color(), a in position(), b in position()) {
+        let pawn = piece(color, PieceKind::Pawn, a);
+        if pawn.can_capture(b) {
+            prop_assert_eq!(a.0.abs_diff(b.0), 1);
+            match color {
+                Color::White => prop_assert_eq!(b.1, a.1 + 1),
+                Color::Black => prop_assert_eq!(b.1 + 1, a.1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_opposite_pawns_capture_each_other_back(a in position(), b in position()) {
+        prop_assert_eq!(
+            piece(Color::White, PieceKind::Pawn, a).can_capture(b),
+            piece(Color::Black, PieceKind::Pawn, b).can_capture(a)
+        );
+    }
+
+    #[test]
+    fn test_no_piece_captures_its_own_square(kind in symmetric_kind(), color in color(), a in position()) {
+        let board = Board::builder().piece(color, kind, a).build().unwrap();
+        prop_assert!(!board.can_capture(a, a));
+    }
+}
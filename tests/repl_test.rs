@@ -0,0 +1,41 @@
+mod command;
+
+use crate::command::CommandOutput;
+
+#[test]
+fn test_repl_moves_and_prints_fen() {
+    let cmd = CommandOutput::cargo_run_bin("repl", &["tests/fixtures/ejemplo01.txt"]);
+    let input = "result\nfen\nmove g3 g6\nfen\nmove a1 a2\nquit\n";
+    let cmd_assert = CommandOutput::run_with_stdin(cmd, input).unwrap();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(
+            stdout,
+            "N\
+            8/8/3q4/8/8/6R1/8/8 w - - 0 1\
+            ok\
+            8/8/3q2R1/8/8/8/8/8 b - - 0 1\
+            ERROR: [Empty square]"
+        );
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}
+
+#[test]
+fn test_repl_shows_board() {
+    let cmd = CommandOutput::cargo_run_bin("repl", &["tests/fixtures/ejemplo01.txt"]);
+    let cmd_assert = CommandOutput::run_with_stdin(cmd, "show\n").unwrap();
+    let grid = std::fs::read_to_string("tests/fixtures/ejemplo01.txt")
+        .unwrap()
+        .lines()
+        .collect::<String>();
+
+    if let Some(stdout) = cmd_assert.stdout() {
+        assert!(cmd_assert.success());
+        assert_eq!(stdout, grid);
+    } else {
+        println!("Test skipped: stdout output is empty");
+    }
+}